@@ -0,0 +1,178 @@
+//! Finding lots of different strings in some text at once, with an
+//! Aho-Corasick automaton.
+//!
+//! All the patterns go into one trie, with links from each node to the
+//! longest suffix of it which is also in the trie, so the text only needs
+//! going through once (a byte at a time) however many patterns there are.
+//! Every match is found, including ones which overlap, like "one" and "eight"
+//! in "oneight".
+
+// Where the automaton is, as an index into `next`
+pub type State = usize;
+
+const ROOT: State = 0;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Match<'a, V> {
+    // Byte offsets, end exclusive
+    pub start: usize,
+    pub end: usize,
+    pub value: &'a V,
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton<V> {
+    // The state after each byte, from each state. Every byte goes somewhere,
+    // so there's never any backtracking along the suffix links.
+    next: Vec<[State; 256]>,
+    // The patterns (by index) ending at each state, longest first
+    outputs: Vec<Vec<usize>>,
+    // Each pattern's length and value
+    patterns: Vec<(usize, V)>,
+}
+
+impl<V> Automaton<V> {
+    pub fn new<P: AsRef<[u8]>, I: IntoIterator<Item = (P, V)>>(patterns: I) -> Automaton<V> {
+        let mut automaton = Automaton{
+            next: vec![[ROOT; 256]],
+            outputs: vec![Vec::new()],
+            patterns: Vec::new(),
+        };
+
+        // The trie, where a 0 in `next` means there's no child yet, as
+        // nothing goes back to the root
+        for (pattern, value) in patterns {
+            let pattern = pattern.as_ref();
+            assert!(!pattern.is_empty(), "empty patterns match everywhere");
+
+            let mut state = ROOT;
+            for &b in pattern {
+                if automaton.next[state][b as usize] == ROOT {
+                    automaton.next.push([ROOT; 256]);
+                    automaton.outputs.push(Vec::new());
+                    automaton.next[state][b as usize] = automaton.next.len() - 1;
+                }
+                state = automaton.next[state][b as usize];
+            }
+
+            automaton.outputs[state].push(automaton.patterns.len());
+            automaton.patterns.push((pattern.len(), value));
+        }
+
+        automaton.link();
+        return automaton;
+    }
+
+    // Fills in the missing transitions from the suffix links, breadth first
+    // so that every shorter state is done before the ones which link to it
+    fn link(&mut self) {
+        let mut suffix = vec![ROOT; self.next.len()];
+        let mut queue = std::collections::VecDeque::new();
+
+        for b in 0..256 {
+            let child = self.next[ROOT][b];
+            if child != ROOT {
+                queue.push_back(child);
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            // Shorter matches ending here too
+            let inherited = self.outputs[suffix[state]].clone();
+            self.outputs[state].extend(inherited);
+
+            for b in 0..256 {
+                let child = self.next[state][b];
+                let fallback = self.next[suffix[state]][b];
+                if child == ROOT {
+                    self.next[state][b] = fallback;
+                } else {
+                    suffix[child] = fallback;
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        // Longest first, for whoever only wants one of them
+        for outputs in self.outputs.iter_mut() {
+            outputs.sort_by_key(|&p| std::cmp::Reverse(self.patterns[p].0));
+        }
+    }
+
+    pub fn start(&self) -> State {
+        return ROOT;
+    }
+
+    pub fn step(&self, state: State, byte: u8) -> State {
+        return self.next[state][byte as usize];
+    }
+
+    // The patterns which end at `state`, as their lengths and values
+    pub fn matches(&self, state: State) -> impl Iterator<Item = (usize, &V)> + '_ {
+        return self.outputs[state].iter().map(|&p| (self.patterns[p].0, &self.patterns[p].1));
+    }
+
+    // Every match in `text`, overlapping or not, in order of where they end
+    pub fn find_all<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = Match<'a, V>> + 'a {
+        let mut state = self.start();
+        return text.iter().enumerate().flat_map(move |(i, &b)| {
+            state = self.step(state, b);
+            return self.matches(state).map(move |(len, value)| Match{ start: i + 1 - len, end: i + 1, value });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(automaton: &Automaton<u32>, text: &str) -> Vec<(usize, usize, u32)> {
+        return automaton.find_all(text.as_bytes()).map(|m| (m.start, m.end, *m.value)).collect();
+    }
+
+    #[test]
+    fn overlapping() {
+        let automaton = Automaton::new([("one", 1), ("eight", 8), ("two", 2)]);
+        assert_eq!(found(&automaton, "oneightwo"), vec![(0, 3, 1), (2, 7, 8), (6, 9, 2)]);
+        assert_eq!(found(&automaton, "on e"), vec![]);
+    }
+
+    #[test]
+    fn suffixes_and_prefixes() {
+        // "he" ends inside "she", and "hers" only matches after backing off
+        // from "she" along the suffix links
+        let automaton = Automaton::new([("he", 0), ("she", 1), ("his", 2), ("hers", 3)]);
+        assert_eq!(found(&automaton, "ushers"), vec![(1, 4, 1), (2, 4, 0), (2, 6, 3)]);
+
+        let automaton = Automaton::new([("seven", 7), ("seventeen", 17), ("teen", 10)]);
+        assert_eq!(found(&automaton, "seventeen"), vec![(0, 5, 7), (0, 9, 17), (5, 9, 10)]);
+    }
+
+    #[test]
+    fn against_naive() {
+        let patterns = ["a", "ab", "bab", "bc", "bca", "c", "caa"];
+        let automaton = Automaton::new(patterns.iter().enumerate().map(|(i, p)| (p, i as u32)));
+
+        let text = "abccab bcaab cbabcaa";
+        let mut naive = Vec::new();
+        for end in 1..=text.len() {
+            // Longest first at each end, like the automaton
+            let mut here: Vec<(usize, usize, u32)> = patterns.iter().enumerate()
+                .filter(|(_, p)| text[..end].ends_with(*p))
+                .map(|(i, p)| (end - p.len(), end, i as u32))
+                .collect();
+            here.sort();
+            naive.extend(here);
+        }
+
+        assert_eq!(found(&automaton, text), naive);
+    }
+
+    #[test]
+    fn bytes() {
+        let automaton = Automaton::new([(&[0xffu8, 0x00][..], 1), ("é".as_bytes(), 2)]);
+        let text = [b'x', 0xff, 0x00, 0xc3, 0xa9];
+        let matches: Vec<(usize, u32)> = automaton.find_all(&text).map(|m| (m.start, *m.value)).collect();
+        assert_eq!(matches, vec![(1, 1), (3, 2)]);
+    }
+}
@@ -0,0 +1,142 @@
+//! Known answers, so that changes to a solution can be checked against them.
+//!
+//! Answers are keyed by day, part and a hash of the input, as everyone's input
+//! (and so answer) is different. They're stored one per line as
+//! "day part hash answer", sorted so that the file diffs nicely.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+use crate::solution::{Answer, Part, Result};
+
+// Where answers are kept when a file isn't given explicitly
+pub const DEFAULT_FILE: &str = "answers.txt";
+
+// FNV-1a, which unlike std's hashers is guaranteed to stay the same
+pub fn input_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in input.bytes() {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    return format!("{:016x}", hash);
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    // With the expected answer
+    Fail(String),
+    Unknown,
+}
+
+#[derive(Debug, Default)]
+pub struct Store {
+    answers: BTreeMap<(u32, Part, String), String>,
+}
+
+impl Store {
+    pub fn parse(s: &str) -> Result<Store> {
+        let mut store = Store::default();
+
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+            if fields.len() != 4 {
+                return Err(format!("line {}: expected 'day part hash answer'", i + 1).into());
+            }
+
+            let day = fields[0].parse::<u32>()
+                .map_err(|_| format!("line {}: invalid day '{}'", i + 1, fields[0]))?;
+            let part = fields[1].parse::<Part>()
+                .map_err(|e| format!("line {}: {}", i + 1, e))?;
+
+            store.answers.insert((day, part, fields[2].to_string()), fields[3].to_string());
+        }
+
+        return Ok(store);
+    }
+
+    // A missing file is just an empty store
+    pub fn load(fname: &str) -> Result<Store> {
+        match fs::read_to_string(fname) {
+            Ok(s) => Store::parse(&s).map_err(|e| format!("{}: {}", fname, e).into()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Store::default()),
+            Err(e) => Err(format!("{}: {}", fname, e).into()),
+        }
+    }
+
+    pub fn save(&self, fname: &str) -> Result<()> {
+        let mut out = String::new();
+        for ((day, part, hash), answer) in &self.answers {
+            out += &format!("{} {} {} {}\n", day, part, hash, answer);
+        }
+
+        fs::write(fname, out).map_err(|e| format!("{}: {}", fname, e))?;
+        return Ok(());
+    }
+
+    pub fn get(&self, day: u32, part: Part, hash: &str) -> Option<&str> {
+        return self.answers.get(&(day, part, hash.to_string())).map(|s| s.as_str());
+    }
+
+    pub fn insert(&mut self, day: u32, part: Part, hash: &str, answer: &Answer) {
+        self.answers.insert((day, part, hash.to_string()), answer.to_string());
+    }
+
+    pub fn check(&self, day: u32, part: Part, hash: &str, answer: &Answer) -> Verdict {
+        match self.get(day, part, hash) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(input_hash("ab"), input_hash("ba"));
+    }
+
+    #[test]
+    fn check_answers() {
+        let store = Store::parse("# comment\n7 1 abcd 6440\n7 2 abcd 5905\n").unwrap();
+
+        assert_eq!(store.check(7, Part::One, "abcd", &Answer::Int(6440)), Verdict::Pass);
+        assert_eq!(store.check(7, Part::Two, "abcd", &Answer::Int(1)), Verdict::Fail("5905".to_string()));
+        assert_eq!(store.check(7, Part::One, "ef01", &Answer::Int(6440)), Verdict::Unknown);
+        assert_eq!(store.check(8, Part::One, "abcd", &Answer::Int(6440)), Verdict::Unknown);
+    }
+
+    #[test]
+    fn text_answers() {
+        let mut store = Store::default();
+        store.insert(3, Part::Two, "abcd", &Answer::Text("two words".to_string()));
+
+        let fname = std::env::temp_dir().join(format!("aoc-answers-{}.txt", std::process::id()));
+        let fname = fname.to_str().unwrap();
+        store.save(fname).unwrap();
+        let store = Store::load(fname).unwrap();
+        fs::remove_file(fname).unwrap();
+
+        assert_eq!(store.get(3, Part::Two, "abcd"), Some("two words"));
+    }
+
+    #[test]
+    fn bad_store() {
+        assert!(Store::parse("7 1 abcd\n").is_err());
+        assert!(Store::parse("7 3 abcd 12\n").is_err());
+        assert!(Store::load("/nonexistent/answers.txt").unwrap().get(7, Part::One, "abcd").is_none());
+    }
+}
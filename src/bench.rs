@@ -0,0 +1,206 @@
+//! Timing each phase of a solution over many runs.
+//!
+//! Results can be saved to a file, one line per day and phase, and a later run
+//! compared against them to see what got faster or slower.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::time::Duration;
+
+use crate::solution::{Part, Result, Runner};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part(Part::One), Phase::Part(Part::Two)];
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        return Stats{
+            min: sorted[0],
+            median: sorted[sorted.len() / 2],
+            max: sorted[sorted.len() - 1],
+        };
+    }
+
+    // Megabytes of input per second, going by the median
+    pub fn throughput(&self, input_len: usize) -> f64 {
+        let secs = self.median.as_secs_f64();
+        if secs == 0.0 {
+            return f64::INFINITY;
+        }
+
+        return input_len as f64 / 1e6 / secs;
+    }
+}
+
+// The stats for one day
+pub struct DayBench {
+    pub day: u32,
+    pub input_len: usize,
+    pub phases: Vec<(Phase, Stats)>,
+}
+
+// Runs the whole solution `iters` times, timing each phase. Stops at the
+// first error, as there's no point timing a wrong answer.
+pub fn bench(runner: &dyn Runner, input: &str, iters: usize) -> Result<DayBench> {
+    let mut samples: HashMap<Phase, Vec<Duration>> = HashMap::new();
+
+    for _ in 0..iters.max(1) {
+        let run = runner.run(input, &Part::ALL)?;
+
+        samples.entry(Phase::Parse).or_default().push(run.parse_elapsed);
+        for result in run.parts {
+            if let Err(e) = result.answer {
+                return Err(format!("part {}: {}", result.part, e).into());
+            }
+            samples.entry(Phase::Part(result.part)).or_default().push(result.elapsed);
+        }
+    }
+
+    let phases = Phase::ALL.iter()
+        .map(|phase| (*phase, Stats::new(&samples[phase])))
+        .collect();
+
+    return Ok(DayBench{
+        day: runner.day(),
+        input_len: input.len(),
+        phases,
+    });
+}
+
+// Saved results are lines of "day phase min median max", times in ns
+pub type Baseline = HashMap<(u32, String), Stats>;
+
+pub fn save(fname: &str, benches: &[DayBench]) -> Result<()> {
+    let mut out = String::new();
+    for b in benches {
+        for (phase, stats) in &b.phases {
+            out += &format!("{} {} {} {} {}\n", b.day, phase,
+                stats.min.as_nanos(), stats.median.as_nanos(), stats.max.as_nanos());
+        }
+    }
+
+    fs::write(fname, out).map_err(|e| format!("{}: {}", fname, e))?;
+    return Ok(());
+}
+
+pub fn parse_baseline(s: &str) -> Result<Baseline> {
+    let mut baseline = Baseline::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!("line {}: expected 'day phase min median max'", i + 1).into());
+        }
+
+        let ns = |s: &str| -> Result<Duration> {
+            let v = s.parse::<u64>().map_err(|_| format!("line {}: bad time '{}'", i + 1, s))?;
+            return Ok(Duration::from_nanos(v));
+        };
+
+        let day = fields[0].parse::<u32>().map_err(|_| format!("line {}: bad day", i + 1))?;
+        let stats = Stats{
+            min: ns(fields[2])?,
+            median: ns(fields[3])?,
+            max: ns(fields[4])?,
+        };
+
+        baseline.insert((day, fields[1].to_string()), stats);
+    }
+
+    return Ok(baseline);
+}
+
+pub fn load(fname: &str) -> Result<Baseline> {
+    let s = fs::read_to_string(fname).map_err(|e| format!("{}: {}", fname, e))?;
+    return parse_baseline(&s).map_err(|e| format!("{}: {}", fname, e).into());
+}
+
+// How the median changed from `before`, e.g. "+12.5%" for slower
+pub fn change(before: &Stats, after: &Stats) -> String {
+    let before = before.median.as_secs_f64();
+    let after = after.median.as_secs_f64();
+    if before == 0.0 {
+        return "n/a".to_string();
+    }
+
+    return format!("{:+.1}%", (after - before) / before * 100.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(v: u64) -> Duration {
+        return Duration::from_millis(v);
+    }
+
+    #[test]
+    fn stats() {
+        let s = Stats::new(&[ms(5), ms(1), ms(3), ms(9), ms(2)]);
+        assert_eq!(s, Stats{ min: ms(1), median: ms(3), max: ms(9) });
+
+        // 3MB in 3ms
+        assert_eq!(s.throughput(3_000_000), 1000.0);
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let stats = Stats{ min: ms(1), median: ms(2), max: ms(3) };
+        let benches = vec![DayBench{
+            day: 7,
+            input_len: 100,
+            phases: vec![(Phase::Parse, stats), (Phase::Part(Part::Two), stats)],
+        }];
+
+        let fname = std::env::temp_dir().join(format!("aoc-bench-{}.txt", std::process::id()));
+        let fname = fname.to_str().unwrap();
+        save(fname, &benches).unwrap();
+        let baseline = load(fname).unwrap();
+        fs::remove_file(fname).unwrap();
+
+        assert_eq!(baseline.len(), 2);
+        assert_eq!(baseline[&(7, "parse".to_string())], stats);
+        assert_eq!(baseline[&(7, "part2".to_string())], stats);
+    }
+
+    #[test]
+    fn bad_baseline() {
+        assert!(parse_baseline("7 parse 1 2\n").is_err());
+        assert!(parse_baseline("7 parse 1 x 3\n").is_err());
+    }
+
+    #[test]
+    fn changes() {
+        let a = Stats{ min: ms(1), median: ms(4), max: ms(9) };
+        let b = Stats{ min: ms(1), median: ms(5), max: ms(9) };
+        assert_eq!(change(&a, &b), "+25.0%");
+        assert_eq!(change(&b, &a), "-20.0%");
+    }
+}
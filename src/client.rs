@@ -0,0 +1,349 @@
+//! Downloading puzzle inputs and submitting answers to adventofcode.com.
+//!
+//! Everything needs the `session` cookie from a logged-in browser. Inputs are
+//! cached in `NN/input.txt` so they're only ever downloaded once, and requests
+//! are spaced out so as not to hammer the site.
+//!
+//! The std library doesn't do TLS, so https goes through `curl`. Plain http
+//! (which is what `mockserver` speaks) is handled directly.
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::solution::{Part, Result};
+
+pub const YEAR: u32 = 2023;
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+// The site asks that automated tools say who they are
+const USER_AGENT: &str = "aoc2023 runner (stark3y@gmail.com)";
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Response {
+    pub status: u32,
+    pub body: String,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Wrong(Option<Hint>),
+    // How long is left before another answer can be given
+    TooSoon(Duration),
+    // Either already solved, or part 1 isn't solved yet
+    WrongLevel,
+    // With the text of the page, for anything not recognised
+    Unknown(String),
+}
+
+// Strips tags and squashes whitespace, leaving just the text
+fn page_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            },
+            c if !in_tag => text.push(c),
+            _ => {},
+        }
+    }
+
+    return text.split_whitespace().collect::<Vec<&str>>().join(" ");
+}
+
+// Parses e.g. "1m 2s" or "42s" from "You have 1m 2s left to wait"
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = text[start..].find(" left to wait")? + start;
+
+    let mut secs = 0;
+    for tok in text[start..end].split_whitespace() {
+        // The unit is the last character, which might not be ASCII
+        let (last, _) = tok.char_indices().last()?;
+        let (num, unit) = tok.split_at(last);
+        let num = num.parse::<u64>().ok()?;
+        secs += match unit {
+            "h" => num * 3600,
+            "m" => num * 60,
+            "s" => num,
+            _ => return None,
+        };
+    }
+
+    return Some(Duration::from_secs(secs));
+}
+
+// Works out what happened from the page returned after submitting an answer
+pub fn parse_outcome(html: &str) -> Outcome {
+    // The message is in the <article>, the rest is the site's header and footer
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let text = page_text(article);
+
+    if text.contains("That's the right answer") {
+        return Outcome::Correct;
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        return Outcome::Wrong(hint);
+    } else if text.contains("You gave an answer too recently") {
+        return Outcome::TooSoon(parse_wait(&text).unwrap_or_default());
+    } else if text.contains("You don't seem to be solving the right level") {
+        return Outcome::WrongLevel;
+    }
+
+    return Outcome::Unknown(text);
+}
+
+// Splits "http://host:port" into the address to connect to and Host header
+fn http_host(base_url: &str) -> Result<(String, String)> {
+    let host = base_url.strip_prefix("http://")
+        .ok_or_else(|| format!("unsupported URL '{}'", base_url))?
+        .trim_end_matches('/');
+
+    let addr = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+    return Ok((addr, host.to_string()));
+}
+
+pub fn parse_response(raw: &[u8]) -> Result<Response> {
+    let raw = String::from_utf8_lossy(raw);
+    let (head, body) = raw.split_once("\r\n\r\n").ok_or("malformed HTTP response")?;
+
+    let status_line = head.lines().next().unwrap_or("");
+    let status = status_line.split_whitespace().nth(1)
+        .and_then(|s| s.parse::<u32>().ok())
+        .ok_or_else(|| format!("malformed HTTP status line '{}'", status_line))?;
+
+    if head.lines().any(|l| l.to_ascii_lowercase().starts_with("transfer-encoding: chunked")) {
+        return Err("chunked HTTP responses aren't supported".into());
+    }
+
+    return Ok(Response{ status, body: body.to_string() });
+}
+
+fn http_request(base_url: &str, path: &str, session: &str, form: Option<&str>) -> Result<Response> {
+    let (addr, host) = http_host(base_url)?;
+    let mut stream = TcpStream::connect(&addr).map_err(|e| format!("{}: {}", addr, e))?;
+
+    let method = if form.is_some() { "POST" } else { "GET" };
+    let mut request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: session={}\r\nConnection: close\r\n",
+        method, path, host, USER_AGENT, session
+    );
+    if let Some(form) = form {
+        request += "Content-Type: application/x-www-form-urlencoded\r\n";
+        request += &format!("Content-Length: {}\r\n\r\n{}", form.len(), form);
+    } else {
+        request += "\r\n";
+    }
+
+    stream.write_all(request.as_bytes())?;
+
+    // Connection: close, so the response is everything up to EOF
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw)?;
+
+    return parse_response(&raw);
+}
+
+fn curl_request(url: &str, session: &str, form: Option<&str>) -> Result<Response> {
+    let mut cmd = Command::new("curl");
+    // The cookie goes in on stdin so that it doesn't show up in `ps`
+    cmd.args(["--silent", "--show-error", "--user-agent", USER_AGENT, "--header", "@-"])
+        .args(["--write-out", "\n%{http_code}"]);
+    if let Some(form) = form {
+        cmd.args(["--data", form]);
+    }
+    cmd.arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = cmd.spawn().map_err(|e| format!("https needs curl: {}", e))?;
+    child.stdin.take().unwrap().write_all(format!("Cookie: session={}\n", session).as_bytes())?;
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        return Err(format!("curl: {}", String::from_utf8_lossy(&output.stderr).trim()).into());
+    }
+
+    let out = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = out.rsplit_once('\n').ok_or("no status from curl")?;
+    let status = status.trim().parse::<u32>().map_err(|_| format!("bad status from curl '{}'", status))?;
+
+    return Ok(Response{ status, body: body.to_string() });
+}
+
+pub struct Client {
+    pub base_url: String,
+    pub session: String,
+    // Inputs are cached in `NN/input.txt` under here
+    pub cache_dir: PathBuf,
+    // The least time between two requests to the site
+    pub min_interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, cache_dir: PathBuf) -> Client {
+        return Client{
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            cache_dir,
+            min_interval: Duration::from_secs(5),
+            last_request: None,
+        };
+    }
+
+    // Uses $AOC_SESSION or the contents of `.session` for the cookie, and
+    // $AOC_URL to point somewhere other than the real site.
+    pub fn from_env() -> Result<Client> {
+        let session = match std::env::var("AOC_SESSION") {
+            Ok(s) => s,
+            Err(_) => fs::read_to_string(".session")
+                .map_err(|_| "no session cookie: set AOC_SESSION or put it in .session")?,
+        };
+        let base_url = std::env::var("AOC_URL").unwrap_or(DEFAULT_URL.to_string());
+
+        return Ok(Client::new(&base_url, &session, PathBuf::from(".")));
+    }
+
+    pub fn input_path(&self, day: u32) -> PathBuf {
+        return self.cache_dir.join(format!("{:02}", day)).join("input.txt");
+    }
+
+    pub fn is_cached(&self, day: u32) -> bool {
+        return self.input_path(day).exists();
+    }
+
+    fn request(&mut self, path: &str, form: Option<&str>) -> Result<Response> {
+        if let Some(last) = self.last_request {
+            let since = last.elapsed();
+            if since < self.min_interval {
+                thread::sleep(self.min_interval - since);
+            }
+        }
+        self.last_request = Some(Instant::now());
+
+        let url = format!("{}{}", self.base_url, path);
+        if self.base_url.starts_with("https://") {
+            return curl_request(&url, &self.session, form);
+        }
+
+        return http_request(&self.base_url, path, &self.session, form);
+    }
+
+    // The day's input, from the cache if it's been downloaded before
+    pub fn fetch_input(&mut self, day: u32) -> Result<String> {
+        let path = self.input_path(day);
+        if let Ok(input) = fs::read_to_string(&path) {
+            return Ok(input);
+        }
+
+        let resp = self.request(&format!("/{}/day/{}/input", YEAR, day), None)?;
+        if resp.status != 200 {
+            return Err(format!("day {}: HTTP {}: {}", day, resp.status, resp.body.trim()).into());
+        }
+
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(&path, &resp.body))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        return Ok(resp.body);
+    }
+
+    pub fn submit(&mut self, day: u32, part: Part, answer: &str) -> Result<Outcome> {
+        // Answers are numbers or plain words, but make sure nothing sneaks
+        // another field into the form
+        if answer.is_empty() || !answer.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(format!("can't submit '{}'", answer).into());
+        }
+
+        let form = format!("level={}&answer={}", part, answer);
+        let resp = self.request(&format!("/{}/day/{}/answer", YEAR, day), Some(&form))?;
+        if resp.status != 200 {
+            return Err(format!("day {}: HTTP {}", day, resp.status).into());
+        }
+
+        return Ok(parse_outcome(&resp.body));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcomes() {
+        let page = "<html><main><article><p>That's the right answer!  You are <em>one gold star</em> \
+            closer. <a href=\"/2023/day/1#part2\">[Continue to Part Two]</a></p></article></main></html>";
+        assert_eq!(parse_outcome(page), Outcome::Correct);
+
+        let page = "<article><p>That's not the right answer; your answer is too high.  \
+            Please wait one minute before trying again.</p></article>";
+        assert_eq!(parse_outcome(page), Outcome::Wrong(Some(Hint::TooHigh)));
+
+        let page = "<article><p>That's not the right answer.  If you're stuck...</p></article>";
+        assert_eq!(parse_outcome(page), Outcome::Wrong(None));
+
+        let page = "<article><p>You gave an answer too recently; you have to wait after submitting \
+            an answer before trying again.  You have 1m 2s left to wait.</p></article>";
+        assert_eq!(parse_outcome(page), Outcome::TooSoon(Duration::from_secs(62)));
+
+        let page = "<article><p>You don't seem to be solving the right level.  \
+            Did you already complete it?</p></article>";
+        assert_eq!(parse_outcome(page), Outcome::WrongLevel);
+
+        assert_eq!(parse_outcome("<p>Something <b>else</b></p>"), Outcome::Unknown("Something else".to_string()));
+    }
+
+    #[test]
+    fn waits() {
+        assert_eq!(parse_wait("You have 42s left to wait."), Some(Duration::from_secs(42)));
+        assert_eq!(parse_wait("You have 5m 0s left to wait."), Some(Duration::from_secs(300)));
+        assert_eq!(parse_wait("You have a while left to wait."), None);
+        assert_eq!(parse_wait("You have 5分 left to wait."), None);
+    }
+
+    #[test]
+    fn responses() {
+        let resp = parse_response(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\n1 2 3").unwrap();
+        assert_eq!(resp, Response{ status: 200, body: "1 2 3".to_string() });
+
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\n").is_err());
+        assert!(parse_response(b"HTTP/1.1 OK\r\n\r\n").is_err());
+        assert!(parse_response(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\n").is_err());
+    }
+
+    #[test]
+    fn hosts() {
+        assert_eq!(http_host("http://127.0.0.1:8080/").unwrap(),
+            ("127.0.0.1:8080".to_string(), "127.0.0.1:8080".to_string()));
+        assert_eq!(http_host("http://localhost").unwrap(),
+            ("localhost:80".to_string(), "localhost".to_string()));
+        assert!(http_host("ftp://localhost").is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Finding loops in state machines.
+//!
+//! Plenty of puzzles ask for the state after some enormous number of steps,
+//! which is only possible because the states eventually repeat. Starting from
+//! `x0` and stepping with `f`, the states are x0, f(x0), f(f(x0)), ... which
+//! run through a prefix and then round a cycle forever.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cycle {
+    // Steps before the first state which is on the cycle
+    pub prefix: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    // The earliest step with the same state as step `n`
+    pub fn index_of(&self, n: usize) -> usize {
+        if n < self.prefix {
+            return n;
+        }
+
+        return self.prefix + (n - self.prefix) % self.length;
+    }
+}
+
+// Every state up to the first repeat, so any later one can be looked up
+pub struct History<S> {
+    pub cycle: Cycle,
+    pub states: Vec<S>,
+}
+
+impl<S> History<S> {
+    // The state after `n` steps
+    pub fn at(&self, n: usize) -> &S {
+        return &self.states[self.cycle.index_of(n)];
+    }
+}
+
+// Steps until a state repeats, remembering all of them. Uses more memory than
+// floyd() or brent(), but only calls `f` once per state and keeps the states
+// for looking things up afterwards.
+pub fn detect<S: Clone + Eq + Hash, F: FnMut(&S) -> S>(x0: S, mut f: F) -> History<S> {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = Vec::new();
+
+    let mut x = x0;
+    loop {
+        if let Some(&first) = seen.get(&x) {
+            let cycle = Cycle{ prefix: first, length: states.len() - first };
+            return History{ cycle, states };
+        }
+
+        let next = f(&x);
+        seen.insert(x.clone(), states.len());
+        states.push(x);
+        x = next;
+    }
+}
+
+// Floyd's tortoise and hare, which only ever holds three states
+pub fn floyd<S: Clone + Eq, F: Fn(&S) -> S>(x0: S, f: F) -> Cycle {
+    // Find some step i (a multiple of the length) where x_i == x_2i
+    let mut tortoise = f(&x0);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&f(&hare));
+    }
+
+    // Then walking one from the start and one from i meets at the first
+    // state on the cycle
+    let mut prefix = 0;
+    tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+
+    let mut length = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        length += 1;
+    }
+
+    return Cycle{ prefix, length };
+}
+
+// Brent's algorithm, which also holds just a couple of states but usually
+// calls `f` fewer times than floyd()
+pub fn brent<S: Clone + Eq, F: Fn(&S) -> S>(x0: S, f: F) -> Cycle {
+    // Search in successively doubling windows for the length
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    // With the hare `length` ahead, they meet at the start of the cycle
+    let mut prefix = 0;
+    tortoise = x0.clone();
+    hare = x0;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+
+    return Cycle{ prefix, length };
+}
+
+// The state after `n` steps, without keeping them all
+pub fn nth<S: Clone + Eq, F: Fn(&S) -> S>(x0: S, f: F, n: usize) -> S {
+    let cycle = brent(x0.clone(), &f);
+
+    let mut x = x0;
+    for _ in 0..cycle.index_of(n) {
+        x = f(&x);
+    }
+
+    return x;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, ..., prefix - 1, then round prefix..prefix + length
+    fn machine(prefix: u32, length: u32) -> impl Fn(&u32) -> u32 {
+        return move |&x| if x + 1 < prefix + length { x + 1 } else { prefix };
+    }
+
+    #[test]
+    fn all_agree() {
+        for prefix in 0..8 {
+            for length in 1..8 {
+                let expected = Cycle{ prefix: prefix as usize, length: length as usize };
+                let f = machine(prefix, length);
+
+                assert_eq!(detect(0, &f).cycle, expected);
+                assert_eq!(floyd(0, &f), expected);
+                assert_eq!(brent(0, &f), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn extrapolate() {
+        let f = machine(3, 4);
+        let history = detect(0, &f);
+
+        let mut x = 0;
+        for n in 0..50 {
+            assert_eq!(*history.at(n), x);
+            assert_eq!(nth(0, &f, n), x);
+            x = f(&x);
+        }
+
+        assert_eq!(*history.at(1_000_000_000), 3 + (1_000_000_000 - 3) % 4);
+    }
+
+    #[test]
+    fn fixed_point() {
+        let history = detect(5, |_| 5);
+        assert_eq!(history.cycle, Cycle{ prefix: 0, length: 1 });
+        assert_eq!(history.states, vec![5]);
+
+        assert_eq!(brent(1u64, |x| x * 7 % 13), Cycle{ prefix: 0, length: 12 });
+        assert_eq!(floyd(1u64, |x| x * 7 % 13), Cycle{ prefix: 0, length: 12 });
+    }
+}
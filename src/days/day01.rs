@@ -0,0 +1,473 @@
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::str::FromStr;
+use crate::aho::Automaton;
+use crate::error::{self, ParseErr};
+use crate::runner::{self, STDIN};
+use crate::solution::{Answer, Result, Solution};
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+const WORDS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+const USAGE: &str = "usage: day01 [FILE|-]... [--vocab FILE [--locale NAME[,NAME]...]] [--explain]";
+
+// How the words are matched against the document
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Fold {
+    // Exactly
+    None,
+    // Ignoring the case of A-Z, which keeps it a byte at a time
+    Ascii,
+    // Ignoring case in any script, which needs the lines decoding as UTF-8
+    Unicode,
+}
+
+impl FromStr for Fold {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Fold::None),
+            "ascii" => Ok(Fold::Ascii),
+            "unicode" => Ok(Fold::Unicode),
+            _ => Err(format!("invalid fold '{}', expected none, ascii or unicode", s)),
+        }
+    }
+}
+
+impl Fold {
+    fn apply(&self, s: &str) -> String {
+        return match self {
+            Fold::None => s.to_string(),
+            Fold::Ascii => s.to_ascii_lowercase(),
+            Fold::Unicode => s.to_lowercase(),
+        };
+    }
+}
+
+// The words which spell out digits, on top of the digits themselves. A word
+// can stand for more than one digit, like "twelve" for 12, in which case its
+// first digit counts when it's the first token in a line and its last digit
+// when it's the last.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Vocabulary {
+    pub words: Vec<(String, u32)>,
+    pub fold: Fold,
+}
+
+// One "[name option=value]" section of a vocabulary file
+struct Section<'a> {
+    name: &'a str,
+    fold: Fold,
+    words: Vec<(&'a str, u32)>,
+}
+
+// Vocabulary files have a section per locale, with a word and the number
+// it's for on each line:
+//
+//     # Comments and blank lines are ignored
+//     [de fold=unicode]
+//     eins = 1
+//     zwölf = 12
+fn sections(text: &str) -> std::result::Result<Vec<Section<'_>>, ParseErr> {
+    let mut sections: Vec<Section> = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let header = header.strip_suffix(']')
+                .ok_or_else(|| ParseErr::end_of(text, line, "expected ']'"))?;
+
+            let mut fields = header.split_whitespace();
+            let name = fields.next()
+                .ok_or_else(|| ParseErr::at(text, line, "expected a locale name"))?;
+            if sections.iter().any(|s| s.name == name) {
+                return Err(ParseErr::at(text, name, "locale is already defined"));
+            }
+
+            let mut fold = Fold::None;
+            for option in fields {
+                match option.split_once('=') {
+                    Some(("fold", v)) => {
+                        fold = v.parse().map_err(|e: String| ParseErr::at(text, v, &e))?;
+                    },
+                    _ => return Err(ParseErr::at(text, option, "expected 'fold=none|ascii|unicode'")),
+                }
+            }
+
+            sections.push(Section{ name, fold, words: Vec::new() });
+            continue;
+        }
+
+        let section = sections.last_mut()
+            .ok_or_else(|| ParseErr::at(text, line, "expected a '[locale]' before any words"))?;
+
+        let (word, value) = error::split(text, line, "=")?;
+        let (word, value) = (word.trim(), value.trim());
+        if word.is_empty() {
+            return Err(ParseErr::at(text, line, "expected a word before '='"));
+        }
+
+        section.words.push((word, error::number(text, value)?));
+    }
+
+    return Ok(sections);
+}
+
+impl Vocabulary {
+    // The words from part 2
+    pub fn english() -> Vocabulary {
+        let words = WORDS.iter().zip(0..).map(|(w, v)| (w.to_string(), v)).collect();
+        return Vocabulary{ words, fold: Fold::None };
+    }
+
+    // The words for `locales` from a vocabulary file, which can be left empty
+    // if there's only one to choose from
+    pub fn parse(text: &str, locales: &[&str]) -> Result<Vocabulary> {
+        return Vocabulary::select(sections(text)?, locales);
+    }
+
+    pub fn load(fname: &str, locales: &[&str]) -> Result<Vocabulary> {
+        let text = fs::read_to_string(fname).map_err(|e| format!("{}: {}", fname, e))?;
+        let sections = sections(&text).map_err(|e| e.in_file(fname))?;
+        return Vocabulary::select(sections, locales).map_err(|e| format!("{}: {}", fname, e).into());
+    }
+
+    fn select(sections: Vec<Section>, locales: &[&str]) -> Result<Vocabulary> {
+        let names: Vec<&str> = sections.iter().map(|s| s.name).collect();
+
+        let chosen: Vec<&Section> = match locales {
+            [] if sections.len() == 1 => vec![&sections[0]],
+            [] if sections.is_empty() => return Err("no locales in the vocabulary".into()),
+            [] => return Err(format!("pick a locale with --locale, from {}", names.join(", ")).into()),
+            _ => locales.iter()
+                .map(|&l| sections.iter().find(|s| s.name == l)
+                    .ok_or_else(|| format!("no locale '{}', only {}", l, names.join(", "))))
+                .collect::<std::result::Result<_, _>>()?,
+        };
+
+        let fold = chosen[0].fold;
+        if let Some(other) = chosen.iter().find(|s| s.fold != fold) {
+            return Err(format!("locales {} and {} fold differently", chosen[0].name, other.name).into());
+        }
+
+        let mut words: Vec<(String, u32)> = Vec::new();
+        for section in chosen {
+            for &(word, value) in &section.words {
+                if words.iter().any(|(w, _)| fold.apply(w) == fold.apply(word)) {
+                    return Err(format!("'{}' is in the vocabulary twice", word).into());
+                }
+                words.push((word.to_string(), value));
+            }
+        }
+
+        return Ok(Vocabulary{ words, fold });
+    }
+}
+
+// Something which stands for one or more digits, by where it is in its line
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Token {
+    // Byte offsets, end exclusive
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+}
+
+impl Token {
+    fn first_digit(&self) -> u32 {
+        let mut v = self.value;
+        while v >= 10 {
+            v /= 10;
+        }
+        return v;
+    }
+
+    fn last_digit(&self) -> u32 {
+        return self.value % 10;
+    }
+}
+
+pub struct Scanner {
+    tokens: Automaton<u32>,
+    fold: Fold,
+}
+
+impl Scanner {
+    // Only the digits, for part 1
+    pub fn digits() -> Scanner {
+        return Scanner{ tokens: Automaton::new(DIGITS.iter().zip(0..)), fold: Fold::None };
+    }
+
+    // The digits and the words in `vocabulary`
+    pub fn new(vocabulary: &Vocabulary) -> Scanner {
+        let fold = vocabulary.fold;
+        let digits = DIGITS.iter().map(|d| d.to_string()).zip(0..);
+        let words = vocabulary.words.iter().map(|(w, v)| (fold.apply(w), *v));
+        return Scanner{ tokens: Automaton::new(digits.chain(words)), fold };
+    }
+
+    // Every token in `line` (where they overlap too), in order of where they
+    // end and longest first
+    fn tokens(&self, line: &[u8]) -> Vec<Token> {
+        let found = |text: &[u8]| -> Vec<Token> {
+            return self.tokens.find_all(text)
+                .map(|m| Token{ start: m.start, end: m.end, value: *m.value })
+                .collect();
+        };
+
+        match self.fold {
+            Fold::None => found(line),
+            // Which doesn't move anything
+            Fold::Ascii => found(&line.to_ascii_lowercase()),
+            Fold::Unicode => {
+                // Lower case can be a different length, so remember which
+                // bytes of the line each byte of it came from
+                let mut folded = Vec::with_capacity(line.len());
+                let mut spans = Vec::with_capacity(line.len());
+
+                let mut pos = 0;
+                for chunk in line.utf8_chunks() {
+                    for c in chunk.valid().chars() {
+                        let mut buf = [0; 4];
+                        for lower in c.to_lowercase() {
+                            for &b in lower.encode_utf8(&mut buf).as_bytes() {
+                                folded.push(b);
+                                spans.push((pos, pos + c.len_utf8()));
+                            }
+                        }
+                        pos += c.len_utf8();
+                    }
+                    for &b in chunk.invalid() {
+                        folded.push(b);
+                        spans.push((pos, pos + 1));
+                        pos += 1;
+                    }
+                }
+
+                found(&folded).into_iter()
+                    .map(|t| Token{ start: spans[t.start].0, end: spans[t.end - 1].1, value: t.value })
+                    .collect()
+            },
+        }
+    }
+
+    // The tokens starting first and ending last, which can overlap (or be the
+    // same one). Where two start (or end) in the same place, the longer wins.
+    pub fn first_and_last(&self, line: &[u8]) -> Option<(Token, Token)> {
+        let mut found: Option<(Token, Token)> = None;
+
+        for t in self.tokens(line) {
+            let Some((first, last)) = found.as_mut() else {
+                found = Some((t, t));
+                continue;
+            };
+
+            if t.start < first.start || (t.start == first.start && t.end > first.end) {
+                *first = t;
+            }
+            // They come in order of where they end, longest first
+            if t.end > last.end {
+                *last = t;
+            }
+        }
+
+        return found;
+    }
+
+    // A line at a time, so only the longest line needs to fit in memory.
+    // Lines without any tokens count as 0.
+    pub fn calibration_sum<R: BufRead>(&self, mut reader: R) -> io::Result<u64> {
+        let mut sum = 0;
+
+        let mut line = Vec::new();
+        while reader.read_until(b'\n', &mut line)? > 0 {
+            if let Some((first, last)) = self.first_and_last(&line) {
+                sum += calibration_value(&first, &last) as u64;
+            }
+            line.clear();
+        }
+
+        return Ok(sum);
+    }
+
+    // Like calibration_sum, but writes out which tokens were picked in each
+    // line, and then which lines didn't have any
+    pub fn explain<R: BufRead, W: Write>(&self, mut reader: R, out: &mut W) -> io::Result<u64> {
+        let mut sum = 0;
+        let mut missing = Vec::new();
+
+        let mut line = Vec::new();
+        let mut line_no = 0;
+        while reader.read_until(b'\n', &mut line)? > 0 {
+            line_no += 1;
+
+            let text = line.strip_suffix(b"\n").unwrap_or(&line);
+            let text = text.strip_suffix(b"\r").unwrap_or(text);
+
+            match self.first_and_last(text) {
+                Some((first, last)) => {
+                    let value = calibration_value(&first, &last);
+                    writeln!(out, "{}: {}  first {} at {}..{}, last {} at {}..{} = {}",
+                        line_no, highlight(text, &first, &last),
+                        show(text, &first), first.start, first.end,
+                        show(text, &last), last.start, last.end, value)?;
+                    sum += value as u64;
+                },
+                None => {
+                    writeln!(out, "{}: {}  no digits = 0", line_no, String::from_utf8_lossy(text))?;
+                    missing.push(line_no);
+                },
+            }
+            line.clear();
+        }
+
+        writeln!(out, "sum {} from {} line(s)", sum, line_no)?;
+        if !missing.is_empty() {
+            let lines: Vec<String> = missing.iter().map(|n| n.to_string()).collect();
+            writeln!(out, "{} line(s) without any digits: {}", missing.len(), lines.join(", "))?;
+        }
+
+        return Ok(sum);
+    }
+}
+
+// The first token's first digit and the last token's last digit
+fn calibration_value(first: &Token, last: &Token) -> u32 {
+    return first.first_digit() * 10 + last.last_digit();
+}
+
+fn show(line: &[u8], token: &Token) -> String {
+    return format!("{:?}", String::from_utf8_lossy(&line[token.start..token.end]));
+}
+
+// The line with the first token in [] and the last in (), which can overlap
+// like "[on(e]ight)", or just [] if they're the same one
+fn highlight(line: &[u8], first: &Token, last: &Token) -> String {
+    let mut marks = vec![(first.start, 1, "["), (first.end, 0, "]")];
+    if first != last {
+        marks.extend([(last.start, 1, "("), (last.end, 0, ")")]);
+    }
+    // Closing before opening where they meet
+    marks.sort();
+
+    let mut out = String::new();
+    let mut pos = 0;
+    for (at, _, mark) in marks {
+        out += &String::from_utf8_lossy(&line[pos..at]);
+        out += mark;
+        pos = at;
+    }
+    out += &String::from_utf8_lossy(&line[pos..]);
+
+    return out;
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+
+    // Scanned as bytes, without splitting it up first
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        return Ok(input.to_string());
+    }
+
+    fn part1(document: &Self::Input) -> Result<Answer> {
+        return Ok(Scanner::digits().calibration_sum(document.as_bytes())?.into());
+    }
+
+    fn part2(document: &Self::Input) -> Result<Answer> {
+        let scanner = Scanner::new(&Vocabulary::english());
+        return Ok(scanner.calibration_sum(document.as_bytes())?.into());
+    }
+}
+
+// The calibration sum of each file (streamed, so they can be huge), or how
+// it was worked out with `explain`
+fn run_files(fnames: &[String], scanner: &Scanner, explain: bool) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+
+    for fname in fnames {
+        let reader: Box<dyn BufRead> = if fname == STDIN {
+            Box::new(io::stdin().lock())
+        } else {
+            let file = fs::File::open(fname).map_err(|e| format!("{}: {}", fname, e))?;
+            Box::new(BufReader::new(file))
+        };
+
+        if explain {
+            if fnames.len() > 1 {
+                writeln!(stdout, "{}:", fname)?;
+            }
+            scanner.explain(reader, &mut stdout).map_err(|e| format!("{}: {}", fname, e))?;
+            continue;
+        }
+
+        let sum = scanner.calibration_sum(reader).map_err(|e| format!("{}: {}", fname, e))?;
+        if fnames.len() > 1 {
+            writeln!(stdout, "{}: {}", fname, sum)?;
+        } else {
+            writeln!(stdout, "{}", sum)?;
+        }
+    }
+
+    return Ok(());
+}
+
+fn run(args: &[String]) -> Result<()> {
+    let mut fnames = Vec::new();
+    let mut vocab = None;
+    let mut locales = Vec::new();
+    let mut explain = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--vocab" => {
+                vocab = Some(iter.next().ok_or("--vocab needs a value")?);
+            },
+            "--locale" => {
+                let value = iter.next().ok_or("--locale needs a value")?;
+                locales.extend(value.split(','));
+            },
+            "--explain" => explain = true,
+            _ => fnames.push(arg.to_string()),
+        }
+    }
+    if fnames.is_empty() {
+        fnames.push(STDIN.to_string());
+    }
+
+    if vocab.is_none() && !locales.is_empty() {
+        return Err(format!("--locale needs --vocab\n{}", USAGE).into());
+    }
+    if vocab.is_none() && !explain {
+        return runner::day_main(1);
+    }
+    if fnames.iter().any(|f| f.starts_with("--")) {
+        return Err(format!("only --locale goes with --vocab and --explain\n{}", USAGE).into());
+    }
+
+    let vocabulary = match vocab {
+        Some(vocab) => Vocabulary::load(vocab, &locales)?,
+        None => Vocabulary::english(),
+    };
+    return run_files(&fnames, &Scanner::new(&vocabulary), explain);
+}
+
+// Entry point for the `day01` binary, which is like the other days' unless
+// it's given a vocabulary file for the words, or asked to explain its answer
+pub fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    return runner::report(run(&args));
+}
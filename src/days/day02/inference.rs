@@ -0,0 +1,169 @@
+//! Working out what was in the bag from the hands that came out of it.
+//!
+//! Each hand is a handful of cubes grabbed at random, which all go back in
+//! the bag before the next one. There are two ways to model a handful: cube
+//! by cube, putting each one back before taking the next (so a hand's counts
+//! are multinomial), or all at once (multivariate hypergeometric). The second
+//! is what the puzzle describes, but the first is simpler, and the two agree
+//! for big bags.
+//!
+//! For a given number of cubes in the bag, the log-likelihood of a bag splits
+//! into a concave function of each colour's count, so the most likely bag
+//! can be built up a cube at a time, always adding whichever colour makes the
+//! hands most likely.
+
+use std::str::FromStr;
+use super::{Cubes, Game};
+
+const TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Model {
+    // Cube by cube, so a hand can have more of a colour than the bag
+    WithReplacement,
+    // The whole hand at once
+    WithoutReplacement,
+}
+
+impl FromStr for Model {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "with" => Ok(Model::WithReplacement),
+            "without" => Ok(Model::WithoutReplacement),
+            _ => Err(format!("invalid model '{}', expected with or without", s)),
+        }
+    }
+}
+
+fn ln_factorial(n: u64) -> f64 {
+    return (2..=n).map(|i| (i as f64).ln()).sum();
+}
+
+fn ln_choose(n: u64, k: u64) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    return ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k);
+}
+
+// How likely one hand is from `bag`
+fn ln_hand(hand: &Cubes, bag: &Cubes, model: Model) -> f64 {
+    let (k, n) = (hand.total(), bag.total());
+
+    return match model {
+        Model::WithReplacement => {
+            let mut ln_p = ln_factorial(k);
+            for (colour, h) in hand.iter() {
+                if h == 0 {
+                    continue;
+                }
+                let in_bag = bag.get(colour);
+                if in_bag == 0 {
+                    return f64::NEG_INFINITY;
+                }
+                ln_p += h as f64 * (in_bag as f64 / n as f64).ln() - ln_factorial(h as u64);
+            }
+            ln_p
+        },
+        Model::WithoutReplacement => {
+            // Which can't happen, and -inf - -inf would be NaN
+            if k > n || !bag.covers(hand) {
+                return f64::NEG_INFINITY;
+            }
+            let ways: f64 = hand.iter().map(|(colour, h)| ln_choose(bag.get(colour) as u64, h as u64)).sum();
+            ways - ln_choose(n, k)
+        },
+    };
+}
+
+// The natural log of the chance of the game's hands coming out of `bag`,
+// which is -inf if they can't
+pub fn ln_likelihood(game: &Game, bag: &Cubes, model: Model) -> f64 {
+    return game.hands.iter().map(|hand| ln_hand(hand, bag, model)).sum();
+}
+
+pub fn probability(game: &Game, bag: &Cubes, model: Model) -> f64 {
+    return ln_likelihood(game, bag, model).exp();
+}
+
+// How much more likely the hands are with one more of a colour, when there
+// are already `n` of it
+fn gain(hands: &[&Cubes], colour: &str, n: u32, model: Model) -> f64 {
+    return match model {
+        Model::WithReplacement => {
+            let seen: u32 = hands.iter().map(|h| h.get(colour)).sum();
+            seen as f64 * ((n + 1) as f64 / n as f64).ln()
+        },
+        Model::WithoutReplacement => {
+            hands.iter()
+                .map(|h| ((n + 1) as f64 / (n + 1 - h.get(colour)) as f64).ln())
+                .sum()
+        },
+    };
+}
+
+// The most likely bag of `size` cubes for all the games' hands together (as
+// if they all came from the same bag), or None if there can't be that few.
+// Colours which were never seen aren't in it, and where adding one of either
+// of two colours would be as likely, it goes to the first alphabetically.
+pub fn mle(games: &[Game], size: u32, model: Model) -> Option<Cubes> {
+    let hands: Vec<&Cubes> = games.iter().flat_map(|g| g.hands.iter()).collect();
+
+    // As few as there can be of each colour seen: one, or as many as came
+    // out at once
+    let mut seen = Cubes::default();
+    for hand in &hands {
+        seen.at_least(hand);
+    }
+    let mut bag = Cubes::default();
+    for (colour, most) in seen.iter().filter(|&(_, most)| most > 0) {
+        let least = match model {
+            Model::WithReplacement => 1,
+            Model::WithoutReplacement => most,
+        };
+        bag.0.insert(colour.to_string(), least);
+    }
+
+    if bag.total() > size as u64 || (bag.0.is_empty() && size > 0) {
+        return None;
+    }
+
+    for _ in bag.total()..size as u64 {
+        let mut best: Option<(&String, f64)> = None;
+        for (colour, &n) in bag.0.iter() {
+            let g = gain(&hands, colour, n, model);
+            if best.is_none_or(|(_, b)| g > b) {
+                best = Some((colour, g));
+            }
+        }
+
+        let colour = best.unwrap().0.clone();
+        *bag.0.get_mut(&colour).unwrap() += 1;
+    }
+
+    return Some(bag);
+}
+
+// The most likely bag of any size up to `max_size`, with its log-likelihood.
+// Of equally likely sizes it's the smallest (with replacement, doubling
+// everything changes nothing), but the likelihood can also keep going up with
+// the size, in which case it's `max_size`.
+pub fn mle_any_size(games: &[Game], max_size: u32, model: Model) -> Option<(Cubes, f64)> {
+    let mut best: Option<(Cubes, f64)> = None;
+
+    for size in 0..=max_size {
+        let Some(bag) = mle(games, size, model) else {
+            continue;
+        };
+
+        let ln_l: f64 = games.iter().map(|g| ln_likelihood(g, &bag, model)).sum();
+        // Allowing for rounding, when they're really the same
+        if best.as_ref().is_none_or(|(_, b)| ln_l > *b + TOLERANCE) {
+            best = Some((bag, ln_l));
+        }
+    }
+
+    return best;
+}
@@ -0,0 +1,339 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::result;
+use crate::error::{self, ParseErr};
+use crate::runner::{self, STDIN};
+use crate::solution::{Answer, Result, Solution};
+use inference::Model;
+
+pub mod inference;
+
+// The bag from part 1
+const PUZZLE_BAG: &str = "12 red, 13 green, 14 blue";
+
+const USAGE: &str = "usage: day02 [FILE|-]... [--bag CUBES]... [--bags FILE] [--min]
+             [--probability with|without] [--mle with|without [--max-size N]]";
+
+// The biggest bag `--mle` looks at by default
+const MAX_SIZE: u32 = 100;
+
+// How many cubes there are of each colour, in a hand or a bag
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cubes(BTreeMap<String, u32>);
+
+impl Cubes {
+    // None of a colour it doesn't know about
+    pub fn get(&self, colour: &str) -> u32 {
+        return self.0.get(colour).copied().unwrap_or(0);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        return self.0.iter().map(|(c, &n)| (c.as_str(), n));
+    }
+
+    pub fn total(&self) -> u64 {
+        return self.0.values().map(|&n| n as u64).sum();
+    }
+
+    // The number of each colour multiplied together, for part 2, or None if
+    // that's too big
+    pub fn power(&self) -> Option<u64> {
+        return self.0.values().try_fold(1u64, |p, &n| p.checked_mul(n as u64));
+    }
+
+    // Whether there are at least as many of each colour as in `other`
+    pub fn covers(&self, other: &Cubes) -> bool {
+        return other.iter().all(|(c, n)| self.get(c) >= n);
+    }
+
+    // As many of each colour as the most in either
+    pub fn at_least(&mut self, other: &Cubes) {
+        for (colour, n) in other.iter() {
+            let have = self.0.entry(colour.to_string()).or_insert(0);
+            *have = (*have).max(n);
+        }
+    }
+}
+
+impl fmt::Display for Cubes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cubes: Vec<String> = self.iter().map(|(c, n)| format!("{} {}", n, c)).collect();
+        write!(f, "{}", cubes.join(", "))
+    }
+}
+
+// Like "3 blue, 4 red", which must be a slice of `input` for the errors
+fn parse_cubes(input: &str, s: &str) -> result::Result<Cubes, ParseErr> {
+    let parts: Vec<&str> = s.split_whitespace().collect();
+    let mut cubes = Cubes::default();
+
+    for i in (0..parts.len()).step_by(2) {
+        let n_cubes = error::number(input, parts[i])?;
+        let colour = parts.get(i + 1)
+            .ok_or_else(|| ParseErr::end_of(input, s, "expected a colour"))?
+            .trim_end_matches(',');
+
+        if cubes.0.insert(String::from(colour), n_cubes).is_some() {
+            return Err(ParseErr::at(input, colour, "colour is already in the hand"));
+        }
+    }
+
+    return Ok(cubes);
+}
+
+// A bag per line, skipping blank lines and "#" comments
+pub fn parse_bags(input: &str) -> result::Result<Vec<Cubes>, ParseErr> {
+    let mut bags = Vec::new();
+    for line in input.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        bags.push(parse_cubes(input, line)?);
+    }
+
+    return Ok(bags);
+}
+
+impl std::str::FromStr for Cubes {
+    type Err = ParseErr;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        return parse_cubes(s, s);
+    }
+}
+
+pub fn puzzle_bag() -> Cubes {
+    return PUZZLE_BAG.parse().unwrap();
+}
+
+// Why a game couldn't have come from a bag: the hand needing the most more
+// of a colour than the bag has
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortfall {
+    // From 0
+    pub hand: usize,
+    pub colour: String,
+    pub seen: u32,
+    pub in_bag: u32,
+}
+
+#[derive(Debug)]
+pub struct Game {
+    pub id: u32,
+    pub hands: Vec<Cubes>,
+}
+
+fn parse_game(input: &str, line: &str) -> result::Result<Game, ParseErr> {
+    let (game_id, rest) = error::split(input, line, ":")?;
+
+    let id = match game_id.split_once(' ') {
+        Some(("Game", id)) => error::number(input, id.trim())?,
+        _ => return Err(ParseErr::at(input, game_id, "expected 'Game N'")),
+    };
+
+    let mut game = Game {
+        id,
+        hands: Vec::new(),
+    };
+
+    for hand in rest.split(';') {
+        game.hands.push(parse_cubes(input, hand)?);
+    }
+
+    return Ok(game);
+}
+
+impl Game {
+    // The fewest cubes of each colour the bag could have had
+    pub fn min_bag(&self) -> Cubes {
+        let mut bag = Cubes::default();
+        for hand in &self.hands {
+            bag.at_least(hand);
+        }
+
+        return bag;
+    }
+
+    pub fn possible_with(&self, bag: &Cubes) -> bool {
+        return self.hands.iter().all(|hand| bag.covers(hand));
+    }
+
+    // None if the game is possible. Otherwise the first hand which is the
+    // furthest over.
+    pub fn tightest_hand(&self, bag: &Cubes) -> Option<Shortfall> {
+        let mut tightest: Option<Shortfall> = None;
+
+        for (i, hand) in self.hands.iter().enumerate() {
+            for (colour, seen) in hand.iter() {
+                let in_bag = bag.get(colour);
+                if seen <= in_bag {
+                    continue;
+                }
+
+                if tightest.as_ref().is_none_or(|t| seen - in_bag > t.seen - t.in_bag) {
+                    tightest = Some(Shortfall{ hand: i, colour: colour.to_string(), seen, in_bag });
+                }
+            }
+        }
+
+        return tightest;
+    }
+}
+
+// The IDs of the games which could have come from `bag`
+pub fn possible_ids(games: &Vec<Game>, bag: &Cubes) -> Vec<u32> {
+    return games.iter().filter(|g| g.possible_with(bag)).map(|g| g.id).collect();
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+    type Input = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut games = Vec::new();
+        for line in input.lines() {
+            games.push(parse_game(input, line)?);
+        }
+
+        return Ok(games);
+    }
+
+    fn part1(games: &Self::Input) -> Result<Answer> {
+        let id_sum: u64 = possible_ids(games, &puzzle_bag()).iter().map(|&id| id as u64).sum();
+        return Ok(id_sum.into());
+    }
+
+    fn part2(games: &Self::Input) -> Result<Answer> {
+        let mut power_sum: u64 = 0;
+        for game in games {
+            let power = game.min_bag().power()
+                .ok_or_else(|| format!("the power of game {} is too big", game.id))?;
+            power_sum = power_sum.checked_add(power).ok_or("the sum of the powers is too big")?;
+        }
+        return Ok(power_sum.into());
+    }
+}
+
+// Which games each bag could have been used for, what rules out the others,
+// and how likely each game was if there's a model for it
+fn report_bags(games: &Vec<Game>, bags: &[Cubes], model: Option<Model>) {
+    for bag in bags {
+        let ids = possible_ids(games, bag);
+        let list: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        println!("bag {}: {} of {} games possible ({}), sum {}",
+            bag, ids.len(), games.len(), list.join(", "), ids.iter().map(|&id| id as u64).sum::<u64>());
+
+        for game in games {
+            if let Some(s) = game.tightest_hand(bag) {
+                println!("  game {}: hand {} has {} {}, {} more than the bag",
+                    game.id, s.hand + 1, s.seen, s.colour, s.seen - s.in_bag);
+            }
+            if let Some(model) = model {
+                println!("  game {}: probability {:.3e}", game.id, inference::probability(game, bag, model));
+            }
+        }
+    }
+}
+
+fn report_min_bags(games: &Vec<Game>) {
+    for game in games {
+        let bag = game.min_bag();
+        let power = bag.power().map_or("too big".to_string(), |p| p.to_string());
+        println!("game {}: at least {} (power {})", game.id, bag, power);
+    }
+}
+
+// The most likely bag for each game, and for all of them coming from one bag
+fn report_mle(games: &[Game], model: Model, max_size: u32) {
+    let show = |what: &str, games: &[Game]| {
+        match inference::mle_any_size(games, max_size, model) {
+            Some((bag, ln_l)) => {
+                let limit = if bag.total() == max_size as u64 { ", the most allowed" } else { "" };
+                println!("{}: most likely {} ({} cubes{}), ln L {:.3}", what, bag, bag.total(), limit, ln_l);
+            },
+            None => println!("{}: no bag of up to {} cubes", what, max_size),
+        }
+    };
+
+    for game in games {
+        show(&format!("game {}", game.id), std::slice::from_ref(game));
+    }
+    show("all games", games);
+}
+
+fn run(args: &[String]) -> Result<()> {
+    let mut fnames = Vec::new();
+    let mut bags = Vec::new();
+    let mut min = false;
+    let mut probability = None;
+    let mut mle = None;
+    let mut max_size = MAX_SIZE;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--bag" => {
+                let value = iter.next().ok_or("--bag needs a value")?;
+                bags.push(value.parse::<Cubes>().map_err(|e| format!("--bag: {}", e))?);
+            },
+            "--bags" => {
+                let fname = iter.next().ok_or("--bags needs a value")?;
+                let input = runner::read_input(fname)?;
+                bags.extend(parse_bags(&input).map_err(|e| runner::in_file(e.into(), fname))?);
+            },
+            "--min" => min = true,
+            "--probability" => {
+                probability = Some(iter.next().ok_or("--probability needs a value")?.parse::<Model>()?);
+            },
+            "--mle" => {
+                mle = Some(iter.next().ok_or("--mle needs a value")?.parse::<Model>()?);
+            },
+            "--max-size" => {
+                let value = iter.next().ok_or("--max-size needs a value")?;
+                max_size = value.parse().map_err(|_| format!("invalid --max-size '{}'", value))?;
+            },
+            _ => fnames.push(arg.to_string()),
+        }
+    }
+    if fnames.is_empty() {
+        fnames.push(STDIN.to_string());
+    }
+
+    if probability.is_some() && bags.is_empty() {
+        return Err(format!("--probability needs a --bag or --bags\n{}", USAGE).into());
+    }
+    if bags.is_empty() && !min && mle.is_none() {
+        return runner::day_main(2);
+    }
+    if fnames.iter().any(|f| f.starts_with("--")) {
+        return Err(format!("only the bag and inference options go together\n{}", USAGE).into());
+    }
+
+    for fname in &fnames {
+        let input = runner::read_input(fname)?;
+        let games = Day02::parse(&input).map_err(|e| runner::in_file(e, fname))?;
+
+        if fnames.len() > 1 {
+            println!("{}:", fname);
+        }
+        report_bags(&games, &bags, probability);
+        if min {
+            report_min_bags(&games);
+        }
+        if let Some(model) = mle {
+            report_mle(&games, model, max_size);
+        }
+    }
+
+    return Ok(());
+}
+
+// Entry point for the `day02` binary, which is like the other days' unless
+// it's given bags to try the games against, or asked what was in the bag
+pub fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    return runner::report(run(&args));
+}
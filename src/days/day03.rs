@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::option::Option;
+use crate::solution::{Answer, Result, Solution};
+
+// Only returns the fist symbol found
+fn number_touches_symbol(
+    num_coord: &(i32, i32),
+    num: &String,
+    symbol_coords: &HashMap::<(i32, i32), char>
+) -> Option<((i32, i32), char)> {
+    let min_col = num_coord.0 - 1;
+    let max_col = num_coord.0 + num.len() as i32;
+    let min_row = num_coord.1 - 1;
+    let max_row = num_coord.1 + 1;
+
+    for row in min_row..=max_row {
+        for col in min_col..=max_col {
+            // This depends on none of the number's coordinates being
+            // in symbol_coords, but why would they be?
+            if let Some(c) = symbol_coords.get(&(col, row)) {
+                return Some(((col, row), *c));
+            }
+        }
+    }
+
+    return None;
+}
+
+fn find_gears(
+    number_coords: &HashMap::<(i32, i32), String>,
+    symbol_coords: &HashMap::<(i32, i32), char>
+) -> Vec<(u32, u32)> {
+    let mut gear_candidates = HashMap::<(i32, i32), Vec<String>>::new();
+
+    for (coord, number) in number_coords.iter() {
+        // This assumes that a gear number _only_ touches the 
+        // gear. If it touches another symbol, then we might hit that
+        // first and number_touches_symbol will return that instead.
+        if let Some((symbol_coord, '*')) = number_touches_symbol(coord, number, symbol_coords) {
+            gear_candidates.entry(symbol_coord)
+                .or_default()
+                .push(number.to_string());
+        }
+    }
+
+    let mut gears = Vec::new();
+    for (_, numbers) in gear_candidates.iter() {
+        if numbers.len() == 2 {
+            gears.push((
+                    numbers[0].parse::<u32>().unwrap(),
+                    numbers[1].parse::<u32>().unwrap(),
+            ));
+        }
+    }
+
+    return gears;
+}
+
+pub struct Schematic {
+    symbol_coords: HashMap<(i32, i32), char>,
+    number_coords: HashMap<(i32, i32), String>,
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+    type Input = Schematic;
+
+    fn parse(input: &str) -> Result<Schematic> {
+        let mut symbol_coords = HashMap::<(i32, i32), char>::new();
+        let mut number_coords = HashMap::<(i32, i32), String>::new();
+
+        for (row, line) in input.lines().enumerate() {
+            let mut number = String::new();
+            for (col, letter) in line.chars().enumerate() {
+                if letter.is_ascii_digit() {
+                    number.push(letter);
+                } else {
+                    if letter != '.' {
+                        symbol_coords.insert((col as i32, row as i32), letter);
+                    }
+
+                    if !number.is_empty() {
+                        number_coords.insert(
+                            ((col - number.len()) as i32, row as i32),
+                            number,
+                        );
+
+                        number = String::new();
+                    }
+                }
+            }
+
+            if !number.is_empty() {
+                number_coords.insert(
+                    ((line.len() - number.len()) as i32, row as i32),
+                    number,
+                );
+            }
+        }
+
+        return Ok(Schematic{ symbol_coords, number_coords });
+    }
+
+    fn part1(schematic: &Schematic) -> Result<Answer> {
+        let mut sum = 0;
+        for (coords, number) in schematic.number_coords.iter() {
+            if number_touches_symbol(coords, number, &schematic.symbol_coords).is_some() {
+                sum += number.parse::<u32>().unwrap();
+            }
+        }
+
+        return Ok(sum.into());
+    }
+
+    fn part2(schematic: &Schematic) -> Result<Answer> {
+        let gear_sum: u32 = find_gears(&schematic.number_coords, &schematic.symbol_coords)
+            .iter()
+            .map(|g| { g.0 * g.1 })
+            .sum();
+
+        return Ok(gear_sum.into());
+    }
+}
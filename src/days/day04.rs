@@ -0,0 +1,107 @@
+use std::collections::{HashMap, HashSet};
+use std::iter::FromIterator;
+use std::ops::Range;
+use std::result;
+use crate::error::{self, ParseErr};
+use crate::solution::{Answer, Result, Solution};
+
+fn evaluate_wins(
+    card_wins: &Vec<Vec<u32>>,
+    card_n_wins: &mut HashMap<u32, u32>,
+    card: &u32) -> u32 {
+
+    match card_n_wins.get(card) {
+        Some(n) => *n,
+        None => {
+            let wins = &card_wins[(card - 1) as usize];
+            let mut n_wins = wins.len() as u32;
+
+            for win in wins.iter() {
+                n_wins += evaluate_wins(card_wins, card_n_wins, win);
+            }
+
+            // Add result to card_n_wins so we don't have to calculate it again
+            card_n_wins.insert(*card, n_wins);
+
+            return n_wins;
+        }
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+
+    // Tracks which cards are won by each card. Index is card number - 1.
+    type Input = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut card_wins: Vec<Vec<u32>> = Vec::new();
+        let mut cards: Vec<&str> = Vec::new();
+
+        for line in input.lines() {
+            let (card_n, rest) = error::split(input, line, ":")?;
+
+            let (_, n) = error::split(input, card_n, " ")?;
+            let n: u32 = error::number(input, n.trim())?;
+
+            // Card numbers are used as indices later
+            if n as usize != card_wins.len() + 1 {
+                return Err(ParseErr::at(input, card_n, "cards out of order").into());
+            }
+
+            let (winning, card) = error::split(input, rest, "|")?;
+
+            let winning = winning.split_whitespace()
+                .map(|v| error::number(input, v))
+                .collect::<result::Result<Vec<u32>, _>>()?;
+            let winning: HashSet<u32> = HashSet::from_iter(winning);
+
+            let card = card.split_whitespace()
+                .map(|v| error::number(input, v))
+                .collect::<result::Result<Vec<u32>, _>>()?;
+            let card: HashSet<u32> = HashSet::from_iter(card);
+
+            let n_wins = card.intersection(&winning).count();
+
+            let wins = Range{start: n + 1, end: n + 1 + n_wins as u32};
+            card_wins.push(wins.collect());
+            cards.push(card_n);
+        }
+
+        // Which would mean copies of cards that don't exist
+        for (wins, card_n) in card_wins.iter().zip(cards) {
+            if wins.last().is_some_and(|&last| last as usize > card_wins.len()) {
+                return Err(ParseErr::at(input, card_n, "card wins copies past the last card").into());
+            }
+        }
+
+        return Ok(card_wins);
+    }
+
+    fn part1(card_wins: &Self::Input) -> Result<Answer> {
+        let mut total_score = 0;
+
+        for wins in card_wins {
+            let n_wins = wins.len();
+            let score = if n_wins > 0 { 1 << (n_wins - 1) } else { 0 };
+
+            total_score += score;
+        }
+
+        return Ok(total_score.into());
+    }
+
+    fn part2(card_wins: &Self::Input) -> Result<Answer> {
+        // Tracks how many cards are won by a given card number, for memoisation.
+        let mut card_n_wins: HashMap<u32, u32> = HashMap::new();
+
+        let mut total_cards = card_wins.len() as u32;
+        for i in 1..=card_wins.len() {
+            total_cards += evaluate_wins(card_wins, &mut card_n_wins, &(i as u32));
+        }
+
+        return Ok(total_cards.into());
+    }
+}
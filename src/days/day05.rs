@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::result;
+use crate::error::{self, ParseErr};
+use crate::interval::IntervalSet;
+use crate::solution::{Answer, Result, Solution};
+
+#[derive(Debug)]
+struct MapRange {
+    from: Range<u64>,
+    to: u64,
+}
+
+#[derive(Debug)]
+pub struct Map {
+    to: String,
+    ranges: Vec<MapRange>,
+}
+
+fn look_up_in(map: &Map, v: &u64) -> u64 {
+    for range in &map.ranges {
+        if range.from.contains(v) {
+            let out = range.to + (v - range.from.start);
+            return out;
+        }
+    }
+
+    return *v;
+}
+
+// Maps every value in `values` at once. Anything not covered by one of the
+// map's ranges stays where it is, and where they overlap the first one wins
+// like in look_up_in().
+fn range_look_up_in(map: &Map, values: &IntervalSet<u64>) -> IntervalSet<u64> {
+    let mut out = Vec::new();
+    let mut unmapped = values.clone();
+
+    for range in &map.ranges {
+        let from = IntervalSet::from(range.from.clone());
+        for r in unmapped.intersection(&from).ranges() {
+            let start = range.to + (r.start - range.from.start);
+            out.push(start..start + (r.end - r.start));
+        }
+
+        unmapped = unmapped.difference(&from);
+    }
+
+    return unmapped.union(&out.into_iter().collect());
+}
+
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: HashMap<String, Map>,
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+    type Input = Almanac;
+
+    fn parse(input: &str) -> Result<Almanac> {
+        let mut lines = input.lines();
+
+        let line = error::next_line(input, &mut lines, "seeds")?;
+
+        let (_, rest) = error::split(input, line, ": ")?;
+        let seeds = rest.split_whitespace()
+            .map(|v| error::number(input, v))
+            .collect::<result::Result<Vec<u64>, _>>()?;
+
+        _ = lines.next();
+
+        let mut maps = HashMap::new();
+
+        let mut done = false;
+        while !done {
+            let line = error::next_line(input, &mut lines, "a map")?;
+            let (mapping, _) = error::split(input, line, " ")?;
+            let (from, to) = error::split(input, mapping, "-to-")?;
+
+            let mut map = Map{
+                to: to.to_string(),
+                ranges: Vec::new(),
+            };
+            let ranges = &mut map.ranges;
+
+            'map: loop {
+                match lines.next() {
+                    Some(line) => {
+                        if line.is_empty() {
+                            break 'map;
+                        }
+
+                        let parts = line.split_whitespace()
+                            .map(|v| error::number(input, v))
+                            .collect::<result::Result<Vec<u64>, _>>()?;
+                        if parts.len() != 3 {
+                            return Err(ParseErr::at(input, line, "expected three numbers").into());
+                        }
+
+                        let mr = MapRange{
+                            from: Range{ start: parts[1], end: parts[1] + parts[2] },
+                            to: parts[0],
+                        };
+
+                        let pos = ranges.binary_search_by_key(&mr.from.start, |v| v.from.start)
+                            .unwrap_or_else(|e| e);
+                        ranges.insert(pos, mr);
+                    },
+                    None => {
+                        done = true;
+                        break 'map;
+                    },
+                }
+            }
+
+            maps.insert(from.to_string(), map);
+        }
+
+        return Ok(Almanac{ seeds, maps });
+    }
+
+    fn part1(almanac: &Almanac) -> Result<Answer> {
+        let maps = &almanac.maps;
+
+        let mut from = String::from("seed");
+        let mut entries = almanac.seeds.clone();
+        while let Some(m) = maps.get(&from) {
+            entries = entries.iter()
+                .map(|v| look_up_in(m, v))
+                .collect();
+            // I don't know how to manage the lifetime properly, so just
+            // copy
+            from = m.to.to_string();
+        }
+
+        let min = entries.iter().min().ok_or("no seeds")?;
+        return Ok((*min).into());
+    }
+
+    fn part2(almanac: &Almanac) -> Result<Answer> {
+        let maps = &almanac.maps;
+        let seeds = &almanac.seeds;
+
+        if !seeds.len().is_multiple_of(2) {
+            return Err("seeds don't come in pairs".into());
+        }
+
+        let mut values: IntervalSet<u64> = seeds.chunks(2)
+            .map(|pair| pair[0]..pair[0] + pair[1])
+            .collect();
+
+        let mut from = String::from("seed");
+        while let Some(m) = maps.get(&from) {
+            values = range_look_up_in(m, &values);
+
+            // I don't know how to manage the lifetime properly, so just
+            // copy
+            from = m.to.to_string();
+        }
+
+        let min = values.min().ok_or("no seeds")?;
+        return Ok(min.into());
+    }
+}
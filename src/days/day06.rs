@@ -0,0 +1,117 @@
+use std::result;
+use crate::error::{self, ParseErr};
+use crate::solution::{Answer, Result, Solution};
+
+#[derive(Debug)]
+struct Race {
+    time: u64,
+    distance: u64,
+}
+
+impl Race {
+    // Wide enough for part 2's times, which are all the digits run together
+    fn distance_with_hold(&self, hold_time: u64) -> u128 {
+        let speed = hold_time as u128;
+        let remainder = (self.time - hold_time) as u128;
+        return remainder * speed;
+    }
+}
+
+pub struct Races {
+    races: Vec<Race>,
+    // Part 2's one race, with all the digits run together
+    long: Race,
+}
+
+// The distance is hold * (time - hold), a parabola which is highest in the
+// middle, so the winning holds are the ones between where it crosses the
+// record. The first of them is the smaller root of
+// hold^2 - time * hold + distance = 0, and the rest follows by symmetry.
+fn count_wins(race: &Race) -> u64 {
+    let time = race.time;
+    let wins = |hold: u64| race.distance_with_hold(hold) > race.distance as u128;
+
+    if !wins(time / 2) {
+        return 0;
+    }
+
+    // The square root is only approximate, so nudge it onto the real edge
+    let discriminant = (time as f64) * (time as f64) - 4.0 * race.distance as f64;
+    let mut first = ((time as f64 - discriminant.max(0.0).sqrt()) / 2.0).floor() as u64;
+    first = first.min(time / 2);
+    while first > 0 && wins(first - 1) {
+        first -= 1;
+    }
+    while !wins(first) {
+        first += 1;
+    }
+
+    return time - 2 * first + 1;
+}
+
+// The numbers after the ':', and all of them run together for part 2, which
+// is checked here so that it's an error on the line itself
+fn parse_numbers(input: &str, line: &str, what: &str) -> result::Result<(Vec<u64>, u64), ParseErr> {
+    let (_, rest) = error::split(input, line, ":")?;
+
+    let mut numbers = Vec::new();
+    for v in rest.split_whitespace() {
+        numbers.push(error::number(input, v)?);
+    }
+
+    // Leaving it to the caller to complain about there not being any
+    if numbers.is_empty() {
+        return Ok((numbers, 0));
+    }
+
+    let joined: String = rest.split_whitespace().collect();
+    let long = joined.parse()
+        .map_err(|_| ParseErr::at(input, rest.trim(), &format!("expected the {} run together to fit in 64 bits", what)))?;
+
+    return Ok((numbers, long));
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+    type Input = Races;
+
+    fn parse(input: &str) -> Result<Races> {
+        let mut lines = input.lines();
+
+        let time_line = error::next_line(input, &mut lines, "times")?;
+        let (times, long_time) = parse_numbers(input, time_line, "times")?;
+        if times.is_empty() {
+            return Err(ParseErr::at(input, time_line, "expected at least one race").into());
+        }
+
+        let distance_line = error::next_line(input, &mut lines, "distances")?;
+        let (distances, long_distance) = parse_numbers(input, distance_line, "distances")?;
+
+        if times.len() != distances.len() {
+            return Err(ParseErr::at(input, distance_line, "expected a distance for each time").into());
+        }
+
+        let races = times.iter().zip(&distances)
+            .map(|(&time, &distance)| Race{ time, distance })
+            .collect();
+        let long = Race{ time: long_time, distance: long_distance };
+
+        return Ok(Races{ races, long });
+    }
+
+    fn part1(races: &Races) -> Result<Answer> {
+        let mut total_margin: u64 = 1;
+        for race in &races.races {
+            total_margin = total_margin.checked_mul(count_wins(race))
+                .ok_or("the product of the ways to win is too big")?;
+        }
+
+        return Ok(total_margin.into());
+    }
+
+    fn part2(races: &Races) -> Result<Answer> {
+        return Ok(count_wins(&races.long).into());
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::cmp::Ordering;
+use crate::error::{self, ParseErr};
+use crate::solution::{Answer, Result, Solution};
+
+#[derive(Debug)]
+#[derive(PartialEq, PartialOrd)]
+enum HandType {
+    HighCard = 0,
+    OnePair = 1,
+    TwoPair = 2,
+    ThreeOfAKind = 3,
+    FullHouse = 4,
+    FourOfAKind = 5,
+    FiveOfAKind = 6,
+}
+
+#[derive(Debug)]
+pub struct Hand {
+    // How each card ranks, for breaking ties between hands of the same type
+    ranks: Cards,
+    bid: u32,
+    hand_type: HandType,
+}
+
+// Each card as its place in CARD_ORDER
+type Cards = [u8; 5];
+
+const CARD_ORDER: &str = "0123456789TJQKA";
+const JOKER: u8 = 11;
+
+// Jokers are the lowest card in part 2, below 0, which moves the ones below
+// them up one
+fn jokered_rank(card: u8) -> u8 {
+    return match card {
+        JOKER => 0,
+        c if c < JOKER => c + 1,
+        c => c,
+    };
+}
+
+impl Hand {
+    fn new(cards: Cards, bid: u32) -> Hand {
+        return Hand{
+            hand_type: Hand::get_type(&cards),
+            ranks: cards,
+            bid,
+        };
+    }
+
+    fn get_type(hand: &Cards) -> HandType {
+        let mut cards = HashMap::new();
+
+        for letter in hand {
+            *cards.entry(letter).or_insert(0) += 1
+        }
+        let max = cards.values().max().unwrap();
+
+        match (cards.len(), max) {
+            (5, _) => HandType::HighCard,
+            (4, _) => HandType::OnePair,
+            (3, 2) => HandType::TwoPair,
+            (3, 3) => HandType::ThreeOfAKind,
+            (2, 3) => HandType::FullHouse,
+            (2, 4) => HandType::FourOfAKind,
+            (1, _) => HandType::FiveOfAKind,
+            // Hands are checked to be five valid cards when parsing
+            _ => unreachable!(),
+        }
+    }
+
+    fn compare(self: &Hand, other: &Hand) -> Ordering {
+        if self.hand_type < other.hand_type {
+            return Ordering::Less;
+        } else if self.hand_type > other.hand_type {
+            return Ordering::Greater;
+        }
+
+        return self.ranks.cmp(&other.ranks);
+    }
+
+    fn new_jokered(cards: Cards, bid: u32) -> Hand {
+        return Hand{
+            hand_type: Hand::get_jokered_type(&cards),
+            ranks: cards.map(jokered_rank),
+            bid,
+        };
+    }
+
+    fn get_jokered_type(hand: &Cards) -> HandType {
+        let mut cards = HashMap::new();
+
+        let mut jokers = 0;
+        for letter in hand {
+            if *letter == JOKER {
+                jokers += 1;
+            } else {
+                *cards.entry(letter).or_insert(0) += 1
+            }
+        }
+
+        // This is a bit of a hack, but I'm bored now.
+        if jokers == 5 {
+            cards.insert(&JOKER, 5);
+            jokers = 0;
+        }
+
+        // Assign the jokers to the most plentiful card face
+        let max = cards.values().max().unwrap();
+        for (letter, num) in cards.iter() {
+            if num == max {
+                cards.entry(*letter).and_modify(|v| *v += jokers);
+                break;
+            }
+        }
+
+        // Recalculate the max
+        let max = cards.values().max().unwrap();
+
+        match (cards.len(), max) {
+            (5, _) => HandType::HighCard,
+            (4, _) => HandType::OnePair,
+            (3, 2) => HandType::TwoPair,
+            (3, 3) => HandType::ThreeOfAKind,
+            (2, 3) => HandType::FullHouse,
+            (2, 4) => HandType::FourOfAKind,
+            (1, _) => HandType::FiveOfAKind,
+            // Hands are checked to be five valid cards when parsing
+            _ => unreachable!(),
+        }
+    }
+}
+
+fn total_winnings(hands: &mut Vec<Hand>) -> u32 {
+    hands.sort_by(|a, b| a.compare(b));
+
+    let mut score = 0;
+    for (rank, hand) in hands.iter().enumerate() {
+        score += (rank as u32 + 1) * hand.bid;
+    }
+
+    return score;
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+
+    // (hand, bid)
+    type Input = Vec<(Cards, u32)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut hands = Vec::new();
+
+        for line in input.lines() {
+            let (hand, bid) = error::split(input, line, " ")?;
+
+            let cards: Vec<u8> = hand.chars()
+                .map(|c| CARD_ORDER.find(c).map(|i| i as u8))
+                .collect::<Option<_>>()
+                .ok_or_else(|| ParseErr::at(input, hand, "expected a hand of five cards"))?;
+            let cards: Cards = cards.try_into()
+                .map_err(|_| ParseErr::at(input, hand, "expected a hand of five cards"))?;
+
+            hands.push((cards, error::number(input, bid)?));
+        }
+
+        return Ok(hands);
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        let mut hands = Vec::new();
+        for (hand, bid) in input {
+            hands.push(
+                Hand::new(
+                    *hand,
+                    *bid
+                )
+            );
+        }
+
+        return Ok(total_winnings(&mut hands).into());
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        let mut jokered_hands = Vec::new();
+        for (hand, bid) in input {
+            jokered_hands.push(
+                Hand::new_jokered(
+                    *hand,
+                    *bid
+                )
+            )
+        }
+
+        return Ok(total_winnings(&mut jokered_hands).into());
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use regex::Regex;
+use crate::cycle;
+use crate::error::{self, ParseErr};
+use crate::numtheory::{crt, first_at_least};
+use crate::solution::{Answer, Result, Solution};
+
+// Node indices, from the names in the input
+type Node = usize;
+
+// The number of moves to the first goal, or None if the route goes round in
+// a loop which doesn't have one
+fn run_route(
+    network: &Network,
+    start: Node,
+    goal_fn: fn(&String) -> bool
+) -> Option<usize> {
+    let route = &network.route;
+    let history = cycle::detect((start, 0), |&(node, i)| {
+        return (network.next[node][route[i]], (i + 1) % route.len());
+    });
+
+    // Up to and including getting back round to the first repeated state
+    return (1..=history.states.len())
+        .find(|&t| goal_fn(&network.names[history.states[history.cycle.index_of(t)].0]));
+}
+
+// When a ghost is at a goal node: at each time in `prefix` and then at
+// `residues` (mod `period`) forever, from `cycle_start` onwards.
+struct Hits {
+    prefix: Vec<usize>,
+    cycle_start: usize,
+    period: usize,
+    residues: Vec<usize>,
+}
+
+// Walks from `start` until the (node, position in route) state repeats, at
+// which point the ghost is in a loop it'll never leave.
+fn find_hits(
+    network: &Network,
+    start: Node,
+    goal_fn: fn(&String) -> bool
+) -> Hits {
+    let route = &network.route;
+    let history = cycle::detect((start, 0), |&(node, i)| {
+        return (network.next[node][route[i]], (i + 1) % route.len());
+    });
+
+    let cycle_start = history.cycle.prefix;
+    let period = history.cycle.length;
+
+    let goals = history.states.iter().enumerate()
+        .filter(|(_, &(node, _))| goal_fn(&network.names[node]))
+        .map(|(t, _)| t);
+    let (prefix, in_cycle): (Vec<usize>, Vec<usize>) = goals.partition(|&t| t < cycle_start);
+    let residues = in_cycle.iter().map(|&t| t % period).collect();
+
+    return Hits{ prefix, cycle_start, period, residues };
+}
+
+impl Hits {
+    fn contains(&self, t: usize) -> bool {
+        if t < self.cycle_start {
+            return self.prefix.contains(&t);
+        }
+
+        return self.residues.contains(&(t % self.period));
+    }
+}
+
+// The first time every ghost is at a goal at once
+fn all_at_goal(hits: &Vec<Hits>) -> Option<u64> {
+    // Before they've all settled into their loops, just check each time any
+    // of them is at a goal
+    let mut early: Vec<usize> = hits.iter().flat_map(|h| h.prefix.iter().copied()).collect();
+    early.sort();
+    if let Some(&t) = early.iter().find(|&&t| hits.iter().all(|h| h.contains(t))) {
+        return Some(t as u64);
+    }
+
+    // After that it's a system of congruences, one for each combination of
+    // goals in the loops (which is usually only one)
+    let min = hits.iter().map(|h| h.cycle_start).max()? as i128;
+    let mut best: Option<i128> = None;
+
+    let mut choice = vec![0; hits.len()];
+    'combos: loop {
+        let congruences: Vec<(i128, i128)> = hits.iter().zip(&choice)
+            .filter_map(|(h, &c)| Some((*h.residues.get(c)? as i128, h.period as i128)))
+            .collect();
+        if congruences.len() == hits.len() {
+            if let Some((r, m)) = crt(&congruences) {
+                let t = first_at_least(r, m, min);
+                best = Some(best.map_or(t, |b| b.min(t)));
+            }
+        }
+
+        // Next combination
+        for (c, h) in choice.iter_mut().zip(hits) {
+            *c += 1;
+            if *c < h.residues.len() {
+                continue 'combos;
+            }
+            *c = 0;
+        }
+        break;
+    }
+
+    return best.map(|t| t as u64);
+}
+
+pub struct Network {
+    // 0 for left, 1 for right
+    route: Vec<usize>,
+    names: Vec<String>,
+    // Left and right from each node
+    next: Vec<[Node; 2]>,
+    starts: Vec<Node>,
+}
+
+// Match function for ending Part 2
+fn ends_with_z(s: &String) -> bool {
+    return s.bytes().last().is_some_and(|b| b == b'Z');
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+    type Input = Network;
+
+    fn parse(input: &str) -> Result<Network> {
+        let mut lines = input.lines();
+
+        let route = error::next_line(input, &mut lines, "a route")?;
+        if route.is_empty() {
+            return Err(ParseErr::at(input, route, "expected a route").into());
+        }
+        if let Some(bad) = route.split(['L', 'R']).find(|s| !s.is_empty()) {
+            return Err(ParseErr::at(input, bad, "expected only 'L' and 'R'").into());
+        }
+        let route = route.bytes().map(|b| if b == b'L' { 0 } else { 1 }).collect();
+
+        lines.next();
+
+        let line_re = Regex::new(r"([A-Z0-9]{3}) = \(([A-Z0-9]{3}), ([A-Z0-9]{3})\)").unwrap();
+
+        let mut index: HashMap<&str, Node> = HashMap::new();
+        let mut names = Vec::new();
+        let mut links = Vec::new();
+        for line in lines {
+            let caps = line_re.captures(line)
+                .ok_or_else(|| ParseErr::at(input, line, "expected 'AAA = (BBB, CCC)'"))?;
+
+            let node = caps.get(1).unwrap().as_str();
+            index.insert(node, names.len());
+            names.push(node.to_string());
+            links.push([caps.get(2).unwrap().as_str(), caps.get(3).unwrap().as_str()]);
+        }
+
+        // Everything has to lead somewhere, so the routes can't get lost
+        let mut next = Vec::new();
+        for [left, right] in links {
+            let find = |node: &str| index.get(node).copied()
+                .ok_or_else(|| ParseErr::at(input, node, "unknown node"));
+            next.push([find(left)?, find(right)?]);
+        }
+
+        let starts = (0..names.len()).filter(|&n| names[n].ends_with('A')).collect();
+
+        return Ok(Network{ route, names, next, starts });
+    }
+
+    fn part1(network: &Network) -> Result<Answer> {
+        // The "sample3" input doesn't have an AAA so just guard this
+        // against that.
+        let start = network.names.iter().position(|n| n == "AAA").ok_or("no AAA node")?;
+
+        let moves = run_route(network, start, |n| n == "ZZZ").ok_or("ZZZ can't be reached from AAA")?;
+        return Ok(moves.into());
+    }
+
+    fn part2(network: &Network) -> Result<Answer> {
+        let hits = network.starts.iter()
+            .map(|&s| find_hits(network, s, ends_with_z))
+            .collect();
+
+        let moves = all_at_goal(&hits).ok_or("the ghosts are never all at Z nodes")?;
+        return Ok(moves.into());
+    }
+}
@@ -0,0 +1,69 @@
+use std::result;
+use crate::error::{self, ParseErr};
+use crate::solution::{Answer, Result, Solution};
+
+fn calc_diff(vals: &Vec<i32>) -> Vec<i32> {
+    let mut result = Vec::new();
+
+    for i in 1..vals.len() {
+        result.push(vals[i] - vals[i-1]);
+    }
+
+    return result;
+}
+
+fn extrapolate(vals: &Vec<i32>) -> (i32, i32) {
+    let diff = calc_diff(vals);
+    if diff.iter().all(|v| *v == 0) {
+        // Doesn't actually matter which we return
+        return (vals[0], vals[vals.len()-1]);
+    }
+
+    let (ex_start, ex_end) = extrapolate(&diff);
+
+    return (vals[0] - ex_start, vals[vals.len()-1] + ex_end);
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut histories = Vec::new();
+        for line in input.lines() {
+            let vals = line
+                .split_whitespace()
+                .map(|v| error::number(input, v))
+                .collect::<result::Result<Vec<i32>, _>>()?;
+            if vals.is_empty() {
+                return Err(ParseErr::at(input, line, "expected some numbers").into());
+            }
+
+            histories.push(vals);
+        }
+
+        return Ok(histories);
+    }
+
+    fn part1(histories: &Self::Input) -> Result<Answer> {
+        let mut part1 = 0;
+        for vals in histories {
+            let (_, end) = extrapolate(vals);
+            part1 += end;
+        }
+
+        return Ok(part1.into());
+    }
+
+    fn part2(histories: &Self::Input) -> Result<Answer> {
+        let mut part2 = 0;
+        for vals in histories {
+            let (start, _) = extrapolate(vals);
+            part2 += start;
+        }
+
+        return Ok(part2.into());
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::{HashMap, BTreeSet};
+use crate::direction::Direction;
+use crate::error::ParseErr;
+use crate::grid::Grid;
+use crate::picture::{self, Picture};
+use crate::solution::{Answer, Result, Solution};
+
+type Pipe = [Direction; 2];
+
+fn trace_path(
+    map: &Grid<Option<Pipe>>,
+    start: &(usize, usize)
+) -> Result<Vec<(usize, usize)>> {
+    let mut path = Vec::new();
+
+    let mut current_pos = *start;
+    let mut pipe = map[current_pos].ok_or("no pipe at the start")?;
+    let mut going = pipe[1];
+    loop {
+        path.push(current_pos);
+        let next_pos = map.step(current_pos, going.delta())
+            .ok_or_else(|| format!("pipe leads off the map at {:?}", current_pos))?;
+
+        pipe = map[next_pos]
+            .ok_or_else(|| format!("pipe leads to the ground at {:?}", next_pos))?;
+
+        let coming = going.opposite();
+        if coming == pipe[0] {
+            going = pipe[1];
+        } else if coming == pipe[1] {
+            going = pipe[0];
+        } else {
+            return Err(format!("pipe at {:?} doesn't join the one at {:?}", next_pos, current_pos).into());
+        }
+
+        if next_pos == *start {
+            break;
+        }
+
+        current_pos = next_pos;
+    }
+
+    return Ok(path);
+}
+
+fn is_inside(
+    path: &BTreeSet<&(usize, usize)>,
+    map: &Grid<Option<Pipe>>,
+    cell: &(usize, usize)
+) -> bool {
+    let row = cell.1;
+    let col = cell.0;
+
+    let mut crossings = 0;
+    for i in (0..col).rev() {
+        let check = (i, row);
+        if path.contains(&check) && map[check].is_some_and(|mp| mp.contains(&Direction::North)) {
+            crossings += 1;
+        }
+    }
+
+    return (crossings & 1) == 1;
+}
+
+fn find_inside(
+    path: &Vec<(usize, usize)>,
+    map: &Grid<Option<Pipe>>,
+) -> BTreeSet<(usize, usize)> {
+    let path_set = BTreeSet::from_iter(path.iter());
+    let mut outside: BTreeSet<(usize, usize)> = BTreeSet::new();
+    let mut inside: BTreeSet<(usize, usize)> = BTreeSet::new();
+    let mut to_search: BTreeSet<(usize, usize)> = BTreeSet::new();
+
+    for p in &path_set {
+        to_search.insert(**p);
+    }
+
+    while !to_search.is_empty() {
+        let current = to_search.pop_first().unwrap();
+        for check in map.neighbours4(current) {
+            if path_set.contains(&check) {
+                // We already know 'check' is on path, do nothing
+                continue;
+            } else if outside.contains(&check) {
+                if !path_set.contains(&current) {
+                    outside.insert(current);
+                }
+            } else if inside.contains(&check) {
+                if !path_set.contains(&current) {
+                    inside.insert(current);
+                }
+            } else {
+                to_search.insert(check);
+            }
+        }
+
+        if !path_set.contains(&current) &&
+                !outside.contains(&current) &&
+                !inside.contains(&current) {
+            if is_inside(&path_set, map, &current) {
+                inside.insert(current);
+            } else {
+                outside.insert(current);
+            }
+        }
+    }
+
+    return inside;
+}
+
+// Box drawing characters are much easier to follow than the letters
+fn pipe_char(pipe: &Option<Pipe>) -> char {
+    let Some(ends) = pipe else {
+        return '.';
+    };
+    let has = |dir| ends.contains(&dir);
+
+    return match (has(Direction::North), has(Direction::East), has(Direction::South), has(Direction::West)) {
+        (true, _, true, _) => '│',
+        (_, true, _, true) => '─',
+        (true, true, _, _) => '└',
+        (true, _, _, true) => '┘',
+        (_, true, true, _) => '┌',
+        _ => '┐',
+    };
+}
+
+pub struct Maze {
+    map: Grid<Option<Pipe>>,
+    start: (usize, usize),
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Input = Maze;
+
+    fn parse(input: &str) -> Result<Maze> {
+        let pipe_ends: HashMap<char, [Direction; 2]> = HashMap::from([
+            ('|', [Direction::South, Direction::North]),
+            ('-', [Direction::West, Direction::East]),
+            ('7', [Direction::West, Direction::South]),
+            ('J', [Direction::North, Direction::West]),
+            ('L', [Direction::East, Direction::North]),
+            ('F', [Direction::South, Direction::East]),
+        ]);
+
+        let tiles = Grid::parse(input, input, "|-7JLF.S")?;
+        let mut map = tiles.map(|c| pipe_ends.get(c).copied());
+
+        let start = tiles.find(&'S')
+            .ok_or_else(|| ParseErr::end_of(input, input, "expected a start 'S'"))?;
+        let start_line = input.lines().nth(start.1).unwrap();
+        let start_token = &start_line[start.0..start.0 + 1];
+
+        let mut start_ends = Vec::new();
+
+        for dir in Direction::iter() {
+            if let Some(Some(pipe)) = map.step(start, dir.delta()).map(|check| map[check]) {
+                let entry = dir.opposite();
+                if pipe.contains(&entry) {
+                    start_ends.push(dir);
+                }
+            }
+        }
+
+        if start_ends.len() != 2 {
+            return Err(ParseErr::at(input, start_token, "start must join exactly two pipes").into());
+        }
+
+        for (_, ends) in pipe_ends.iter() {
+            if start_ends.contains(&ends[0]) && start_ends.contains(&ends[1]) {
+                map[start] = Some(*ends);
+            }
+        }
+
+        return Ok(Maze{ map, start });
+    }
+
+    fn part1(maze: &Maze) -> Result<Answer> {
+        let path = trace_path(&maze.map, &maze.start)?;
+        return Ok(path.len().div_ceil(2).into());
+    }
+
+    fn part2(maze: &Maze) -> Result<Answer> {
+        let path = trace_path(&maze.map, &maze.start)?;
+        let inside = find_inside(&path, &maze.map);
+        return Ok(inside.len().into());
+    }
+
+    // The loop, and what's inside it
+    fn draw(maze: &Maze) -> Result<Vec<Picture>> {
+        let path = trace_path(&maze.map, &maze.start)?;
+        let inside = find_inside(&path, &maze.map);
+
+        let mut pic = Picture::new(&maze.map, |_, pipe| pipe_char(pipe));
+        pic.title(&format!("loop of {}, {} inside", path.len(), inside.len()))
+            .colour(path.iter().copied(), picture::YELLOW)
+            .shade(inside, picture::GREEN)
+            .mark([maze.start], 'S', picture::RED);
+
+        return Ok(vec![pic]);
+    }
+}
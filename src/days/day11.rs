@@ -0,0 +1,89 @@
+use crate::geometry::Vec2;
+use crate::grid::Grid;
+use crate::solution::{Answer, Result, Solution};
+
+fn find_galaxies(
+    map: &Grid<char>,
+    empty_cols: &Vec<usize>,
+    empty_rows: &Vec<usize>,
+    stretch_factor: usize,
+) -> Vec<Vec2<usize>> {
+    let mut galaxies: Vec<Vec2<usize>> = Vec::new();
+    let mut y_stretch = 0;
+    for (y, row) in map.rows().enumerate() {
+        if y_stretch < empty_rows.len() && y > empty_rows[y_stretch] {
+            y_stretch += 1;
+        }
+
+        let mut x_stretch = 0;
+        for (x, cell) in row.iter().enumerate() {
+            if x_stretch < empty_cols.len() && x > empty_cols[x_stretch] {
+                x_stretch += 1;
+            }
+
+            if *cell == '#' {
+                galaxies.push(Vec2::new(
+                    x + x_stretch * (stretch_factor - 1),
+                    y + y_stretch * (stretch_factor - 1),
+                ));
+            }
+        }
+    }
+
+    return galaxies;
+}
+
+fn sum_manhattan_distances_pairwise(galaxies: &Vec<Vec2<usize>>) -> u64 {
+    let mut distances = Vec::new();
+    for (i, g1) in galaxies.iter().enumerate() {
+        for g2 in galaxies[i+1..].iter() {
+            distances.push(g1.manhattan(*g2) as u64);
+        }
+    }
+
+    return distances.iter().sum();
+}
+
+pub struct Universe {
+    map: Grid<char>,
+    empty_rows: Vec<usize>,
+    empty_cols: Vec<usize>,
+}
+
+impl Universe {
+    pub fn sum_distances(&self, stretch_factor: usize) -> u64 {
+        let galaxies = find_galaxies(&self.map, &self.empty_cols, &self.empty_rows, stretch_factor);
+        return sum_manhattan_distances_pairwise(&galaxies);
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input = Universe;
+
+    fn parse(input: &str) -> Result<Universe> {
+        let map = Grid::parse(input, input, ".#")?;
+
+        let empty_rows = (0..map.height())
+            .filter(|&y| map.row(y).iter().all(|v| *v == '.'))
+            .collect();
+
+        let empty_cols = (0..map.width())
+            .filter(|&x| map.col(x).all(|v| *v == '.'))
+            .collect();
+
+        return Ok(Universe{ map, empty_rows, empty_cols });
+    }
+
+    fn part1(universe: &Universe) -> Result<Answer> {
+        // Stretch factor 2 for part 1
+        return Ok(universe.sum_distances(2).into());
+    }
+
+    fn part2(universe: &Universe) -> Result<Answer> {
+        // Stretch factor 1000000 for part 2!
+        return Ok(universe.sum_distances(1000000).into());
+    }
+}
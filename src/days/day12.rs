@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::result;
+use crate::error::{self, ParseErr};
+use crate::solution::{Answer, Result, Solution};
+
+fn solve(
+    pattern: &Vec<u8>,
+    groups: &Vec<usize>,
+    min_start: usize,
+    i: usize,
+    memo: &mut HashMap<(usize, usize), u64>) -> u64 {
+
+    //let indent = "  ".repeat(i + 1);
+    //println!("{indent} {i}, {groups:?} {min_start}");
+
+    if let Some(solutions) = memo.get(&(min_start, i)) {
+        //println!("{indent} <-- memo {solutions}");
+        return *solutions;
+    }
+
+    // The rest of the groups, with a gap between each of them
+    let needed = groups[i..].iter().sum::<usize>() + (groups.len() - i - 1);
+    let Some(mut max_start) = pattern.len().checked_sub(needed) else {
+        // They don't fit
+        return 0;
+    };
+
+    // Can't skip the next hash
+    if let Some(next_hash) = pattern[min_start..].iter().position(|&b| b == b'#') {
+        let next_hash = next_hash + min_start;
+        //println!("{indent} next_hash: {next_hash}");
+        max_start = std::cmp::min(max_start, next_hash);
+    }
+
+    let mut solutions = 0;
+
+    for bit in min_start..=max_start {
+        //print!("{indent} bit {bit}: ");
+        // Can it start here?
+        if pattern[bit] == b'.' {
+            //println!("bad start (.)");
+            continue;
+        } else if bit > 0 && pattern[bit - 1] == b'#' {
+            //println!("bad start (#)");
+            continue;
+        }
+
+        // Can it end here?
+        let end = bit + groups[i];
+        if end < pattern.len() && pattern[end] == b'#' {
+            //println!("bad end");
+            continue;
+        }
+
+        // Is the middle OK?
+        if pattern[bit..bit + groups[i]].contains(&b'.') {
+            //println!("bad middle");
+            continue;
+        }
+
+        if i < groups.len() - 1 {
+            //println!("recurse {}", i + 1);
+            solutions += solve(pattern, groups, end + 1, i + 1, memo);
+        } else {
+            // Special case for the last group
+            if let Some(last_hash) = pattern.iter().rposition(|x| *x == b'#') {
+                if last_hash > end {
+                    //println!("hash after {}", bit);
+                    continue;
+                }
+            }
+
+            //println!("OK");
+            solutions += 1;
+        }
+    }
+
+    memo.insert((min_start, i), solutions);
+    //println!("{} <-- {}", indent, solutions);
+    return solutions;
+}
+
+pub struct Record {
+    pattern: Vec<u8>,
+    groups: Vec<usize>,
+}
+
+impl Record {
+    fn arrangements(&self) -> u64 {
+        let mut memo = HashMap::new();
+        return solve(&self.pattern, &self.groups, 0, 0, &mut memo);
+    }
+
+    fn unfold(&self) -> Record {
+        let mut pattern2 = Vec::new();
+        for repeat in 0..5 {
+            for b in &self.pattern {
+                pattern2.push(*b);
+            }
+            if repeat < 4 {
+                pattern2.push(b'?');
+            }
+        }
+        let groups2 = self.groups.repeat(5);
+
+        //println!("pattern2: {}, groups2: {:?}", String::from_utf8_lossy(&pattern2), groups2);
+
+        return Record{
+            pattern: pattern2,
+            groups: groups2,
+        };
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    type Input = Vec<Record>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut records = Vec::new();
+
+        for line in input.lines() {
+            let (pattern, groups) = error::split(input, line, " ")?;
+
+            if let Some(idx) = pattern.find(|c| !"?.#".contains(c)) {
+                return Err(ParseErr::at(input, &pattern[idx..], "expected only '?', '.' and '#'").into());
+            }
+
+            let groups = groups.split(",")
+                .map(|v| error::number(input, v))
+                .collect::<result::Result<Vec<usize>, _>>()?;
+
+            //println!("pattern: {}, groups: {:?}", pattern, groups);
+
+            records.push(Record{
+                pattern: pattern.bytes().collect(),
+                groups,
+            });
+        }
+
+        return Ok(records);
+    }
+
+    fn part1(records: &Self::Input) -> Result<Answer> {
+        let total: u64 = records.iter()
+            .map(|r| r.arrangements())
+            .sum();
+
+        return Ok(total.into());
+    }
+
+    fn part2(records: &Self::Input) -> Result<Answer> {
+        let total2: u64 = records.iter()
+            .map(|r| r.unfold().arrangements())
+            .sum();
+
+        return Ok(total2.into());
+    }
+}
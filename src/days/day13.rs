@@ -0,0 +1,90 @@
+use crate::grid::Grid;
+use crate::solution::{Answer, Result, Solution};
+
+fn reflect(mirror_after: usize, src: usize, max: usize) -> Option<usize> {
+    let distance = mirror_after - src + 1;
+    let dest = mirror_after + distance;
+    if dest < max {
+        return Some(dest);
+    }
+    return None;
+}
+
+// The number of mismatched cells for a vertical mirror after each column.
+// Horizontal mirrors are the same thing on the transposed grid.
+fn mirror_diffs(grid: &Grid<char>) -> Vec<usize> {
+    let mut result = Vec::new();
+    for mirror_after in 0..grid.width() - 1 {
+        let mut mismatches = 0;
+        for src in 0..=mirror_after {
+            if let Some(dst) = reflect(mirror_after, src, grid.width()) {
+                mismatches += grid.col(src).zip(grid.col(dst))
+                    .filter(|(a, b)| a != b)
+                    .count();
+            }
+        }
+
+        result.push(mismatches);
+    }
+
+    return result;
+}
+
+// Part 1 looks for a reflection with zero mismatches, Part 2 for one with
+// exactly one (the smudge). `n` is which pattern it is, from 1, for errors.
+fn summarise(n: usize, g: &Grid<char>, mismatches: usize) -> Result<usize> {
+    let v = mirror_diffs(g);
+    let h = mirror_diffs(&g.transpose());
+
+    let cols = v.iter().position(|&val| val == mismatches);
+    let rows = h.iter().position(|&val| val == mismatches);
+
+    if cols.is_some() && rows.is_some() {
+        return Err(format!("pattern {} has both a vertical and a horizontal mirror", n).into());
+    }
+
+    if let Some(cols) = cols {
+        return Ok(cols + 1);
+    } else if let Some(rows) = rows {
+        return Ok((rows + 1) * 100);
+    }
+
+    return Ok(0);
+}
+
+fn summarise_all(matrices: &[Grid<char>], mismatches: usize) -> Result<usize> {
+    let mut total = 0;
+    for (i, m) in matrices.iter().enumerate() {
+        total += summarise(i + 1, m, mismatches)?;
+    }
+
+    return Ok(total);
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    type Input = Vec<Grid<char>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let grids = input.split("\n\n");
+
+        let mut matrices = Vec::new();
+        for g in grids {
+            matrices.push(Grid::parse(input, g, ".#")?);
+        }
+
+        return Ok(matrices);
+    }
+
+    fn part1(matrices: &Self::Input) -> Result<Answer> {
+        let total = summarise_all(matrices, 0)?;
+        return Ok(total.into());
+    }
+
+    fn part2(matrices: &Self::Input) -> Result<Answer> {
+        let total2 = summarise_all(matrices, 1)?;
+        return Ok(total2.into());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::cycle;
+use crate::grid::Grid;
+use crate::picture::{self, Picture};
+use crate::solution::{Answer, Result, Solution};
+
+// Rolls every round rock as far north as it will go
+fn tilt_north(platform: &mut Grid<char>) {
+    let mut blockages = vec![0; platform.width()];
+
+    for y in 0..platform.height() {
+        for (x, blockage) in blockages.iter_mut().enumerate() {
+            match platform[(x, y)] {
+                '#' => {
+                    *blockage = y + 1;
+                },
+                'O' => {
+                    // Update vacated square, then move rock
+                    platform[(x, y)] = '.';
+                    platform[(x, *blockage)] = 'O';
+
+                    *blockage += 1;
+                },
+                '.' => {
+                    continue;
+                },
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
+// North, West, South, East. Rotating clockwise after each tilt brings the
+// next edge round to the north, and after four it's back where it started.
+fn do_cycle(platform: &mut Grid<char>) {
+    for _ in 0..4 {
+        tilt_north(platform);
+        *platform = platform.rotate_cw();
+    }
+}
+
+// The platform ends up going round a loop of the same few states
+fn spin(platform: &Grid<char>) -> cycle::History<Grid<char>> {
+    return cycle::detect(platform.clone(), |p| {
+        let mut p = p.clone();
+        do_cycle(&mut p);
+        return p;
+    });
+}
+
+fn calc_load(platform: &Grid<char>) -> usize {
+    let mut load = 0;
+    let nrows = platform.height();
+
+    for (i, row) in platform.rows().enumerate() {
+        for val in row.iter() {
+            if *val == 'O' {
+                load += nrows - i;
+            }
+        }
+    }
+
+    return load;
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        return Ok(Grid::parse(input, input, ".#O")?);
+    }
+
+    fn part1(platform: &Self::Input) -> Result<Answer> {
+        let nrows = platform.height();
+
+        let mut last_blockage = vec![0; platform.width()];
+        let mut load = 0;
+        for (i, row) in platform.rows().enumerate() {
+            for (j, val) in row.iter().enumerate() {
+                match val {
+                    '#' => {
+                        last_blockage[j] = i + 1;
+                    },
+                    'O' => {
+                        load += nrows - last_blockage[j];
+                        last_blockage[j] += 1;
+                    },
+                    '.' => {
+                        continue;
+                    },
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        return Ok(load.into());
+    }
+
+    fn part2(platform: &Self::Input) -> Result<Answer> {
+        let history = spin(platform);
+        return Ok(calc_load(history.at(1000000000)).into());
+    }
+
+    // Every spin cycle until it's back to one it's been in before
+    fn draw(platform: &Self::Input) -> Result<Vec<Picture>> {
+        let history = spin(platform);
+
+        let mut frames = Vec::new();
+        for (i, state) in history.states.iter().enumerate() {
+            let rocks = state.iter().filter(|&(_, &c)| c == 'O').map(|(pos, _)| pos);
+
+            let mut pic = Picture::new(state, |_, &c| c);
+            pic.title(&format!("after {} cycles, load {}", i, calc_load(state)))
+                .colour(rocks, picture::YELLOW);
+            frames.push(pic);
+        }
+
+        return Ok(frames);
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use regex::Regex;
+use crate::error::ParseErr;
+use crate::solution::{Answer, Result, Solution};
+
+type Lense = (String, u32);
+
+struct Box {
+    id: u32,
+    lenses: Vec<Lense>,
+}
+
+impl Box {
+    fn new(id: u32) -> Box {
+        return Box{
+            id,
+            lenses: Vec::new(),
+        };
+    }
+
+    fn op_eq(&mut self, lense: Lense) {
+        if let Some(pos) = self.lenses.iter().position(|x| x.0 == lense.0) {
+            self.lenses[pos] = lense;
+        } else {
+            self.lenses.push(lense);
+        }
+    }
+
+    fn op_minus(&mut self, label: &str) {
+        if let Some(pos) = self.lenses.iter().position(|x| x.0 == label) {
+            self.lenses.remove(pos);
+        }
+    }
+
+    fn power(&self) -> u32 {
+        let mut power = 0;
+        for (i, (_, f_l)) in self.lenses.iter().enumerate() {
+            power += (self.id + 1) * (i as u32 + 1) * f_l;
+        }
+        return power;
+    }
+}
+
+fn checksum(s: &str) -> u8 {
+    let mut val: u32 = 0;
+    for b in s.bytes() {
+        val += b as u32;
+        val *= 17;
+        val %= 256;
+    }
+
+    return val as u8;
+}
+
+enum Op {
+    Remove,
+    Insert(u32),
+}
+
+pub struct Step {
+    text: String,
+    label: String,
+    op: Op,
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    type Input = Vec<Step>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let insn_re = Regex::new(r"^([a-z]+)([=-])([0-9])?$").unwrap();
+
+        let mut steps = Vec::new();
+
+        for line in input.lines() {
+            for insn in line.split(",") {
+                let caps = insn_re.captures(insn)
+                    .ok_or_else(|| ParseErr::at(input, insn, "expected 'label-' or 'label=N'"))?;
+
+                let label = caps.get(1).unwrap().as_str();
+
+                let op = caps.get(2).unwrap().as_str();
+                let op = if op == "-" {
+                    Op::Remove
+                } else {
+                    let focal_length = caps.get(3)
+                        .ok_or_else(|| ParseErr::at(input, insn, "expected a focal length"))?
+                        .as_str()
+                        .parse::<u32>()?;
+                    Op::Insert(focal_length)
+                };
+
+                steps.push(Step{
+                    text: insn.to_string(),
+                    label: label.to_string(),
+                    op,
+                });
+            }
+        }
+
+        return Ok(steps);
+    }
+
+    fn part1(steps: &Self::Input) -> Result<Answer> {
+        let mut total: u32 = 0;
+        for step in steps {
+            let csum = checksum(&step.text);
+            total += csum as u32;
+        }
+
+        return Ok(total.into());
+    }
+
+    fn part2(steps: &Self::Input) -> Result<Answer> {
+        let mut boxes = HashMap::new();
+
+        for step in steps {
+            let box_num = checksum(&step.label);
+
+            let b = boxes.entry(box_num).or_insert(Box::new(box_num.into()));
+
+            match step.op {
+                Op::Remove => b.op_minus(&step.label),
+                Op::Insert(focal_length) => b.op_eq((step.label.to_string(), focal_length)),
+            }
+        }
+
+        let mut power = 0;
+        for b in boxes.values() {
+            power += b.power();
+        }
+
+        return Ok(power.into());
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::BTreeSet;
+use crate::direction::Direction;
+use crate::grid::Grid;
+use crate::picture::{self, Picture};
+use crate::solution::{Answer, Result, Solution};
+
+#[derive(Clone, Debug, Ord, Eq, PartialOrd, PartialEq)]
+struct Ray {
+    pos: (usize, usize),
+    dir: Direction,
+}
+
+// The squares the beam goes through
+fn trace_rays(cave: &Grid<char>, first_ray: &Ray) -> BTreeSet<(usize, usize)> {
+    let mut rays = BTreeSet::new();
+    rays.insert(first_ray.clone());
+
+    let mut energized = BTreeSet::new();
+    let mut traced_rays = BTreeSet::new();
+
+    while !rays.is_empty() {
+        let mut ray = rays.pop_last().unwrap();
+
+        'next_ray: while !traced_rays.contains(&ray) {
+            energized.insert(ray.pos);
+            traced_rays.insert(ray.clone());
+
+            let cell = cave[ray.pos];
+            match cell {
+                '|' if ray.dir.is_horizontal() => {
+                    // Split - redirect this ray
+                    ray.dir = Direction::North;
+
+                    // And spawn a new one
+                    rays.insert(Ray{
+                        pos: ray.pos,
+                        dir: Direction::South,
+                    });
+                },
+                '-' if !ray.dir.is_horizontal() => {
+                    // Split - redirect this ray
+                    ray.dir = Direction::West;
+
+                    // And spawn a new one
+                    rays.insert(Ray{
+                        pos: ray.pos,
+                        dir: Direction::East,
+                    });
+                },
+                '/' => {
+                    // Horizontal rays turn left, vertical ones right
+                    ray.dir = if ray.dir.is_horizontal() {
+                        ray.dir.turn_left()
+                    } else {
+                        ray.dir.turn_right()
+                    };
+                },
+                '\\' => {
+                    ray.dir = if ray.dir.is_horizontal() {
+                        ray.dir.turn_right()
+                    } else {
+                        ray.dir.turn_left()
+                    };
+                },
+                _ => {},
+            }
+
+            if let Some(new_pos) = cave.step(ray.pos, ray.dir.delta()) {
+                ray.pos = new_pos;
+            } else {
+                break 'next_ray;
+            }
+        }
+    }
+
+    return energized;
+}
+
+// Every way the beam can come in from the edge, for part 2
+fn edge_rays(cave: &Grid<char>) -> Vec<Ray> {
+    let mut rays = Vec::new();
+
+    for i in 0..cave.height() {
+        rays.push(Ray{ pos: (0, i), dir: Direction::East });
+        rays.push(Ray{ pos: (cave.width() - 1, i), dir: Direction::West });
+    }
+
+    for i in 0..cave.width() {
+        rays.push(Ray{ pos: (i, 0), dir: Direction::South });
+        rays.push(Ray{ pos: (i, cave.height() - 1), dir: Direction::North });
+    }
+
+    return rays;
+}
+
+fn draw_energized(cave: &Grid<char>, ray: &Ray) -> Picture {
+    let energized = trace_rays(cave, ray);
+
+    let mut pic = Picture::new(cave, |_, &c| c);
+    pic.title(&format!("from {:?} going {:?}, {} energized", ray.pos, ray.dir, energized.len()))
+        .shade(energized, picture::YELLOW)
+        .colour([ray.pos], picture::RED);
+
+    return pic;
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        return Ok(Grid::parse(input, input, ".|-/\\")?);
+    }
+
+    fn part1(cave: &Self::Input) -> Result<Answer> {
+        let part1 = trace_rays(cave, &Ray{ pos: (0, 0), dir: Direction::East });
+        return Ok(part1.len().into());
+    }
+
+    fn part2(cave: &Self::Input) -> Result<Answer> {
+        let max_energized = edge_rays(cave).iter()
+            .map(|ray| trace_rays(cave, ray).len())
+            .max()
+            .unwrap();
+
+        return Ok(max_energized.into());
+    }
+
+    // The energized squares for part 1, then for the best start in part 2
+    fn draw(cave: &Self::Input) -> Result<Vec<Picture>> {
+        let best = edge_rays(cave).into_iter()
+            .max_by_key(|ray| trace_rays(cave, ray).len())
+            .unwrap();
+
+        return Ok(vec![
+            draw_energized(cave, &Ray{ pos: (0, 0), dir: Direction::East }),
+            draw_energized(cave, &best),
+        ]);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::direction::Direction;
+use crate::graph;
+use crate::grid::Grid;
+use crate::picture::{self, Picture};
+use crate::solution::{Answer, Result, Solution};
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+struct State {
+    pos: (usize, usize),
+    dir: Direction,
+    straight: usize,
+}
+
+fn allowed_dirs_part1(straight: usize) -> (bool, bool) {
+    return (true, straight < 2);
+}
+
+fn allowed_dirs_part2(straight: usize) -> (bool, bool) {
+    return (straight >= 3, straight < 9);
+}
+
+fn explore(
+    map: &Grid<u8>,
+    allowed_dirs: fn(usize) -> (bool, bool),
+) -> Option<graph::Path<State, u32>> {
+    let goal = (map.width() - 1, map.height() - 1);
+
+    // Need to seed the search with both East and South
+    let starts = [Direction::East, Direction::South]
+        .map(|dir| State{ pos: (0, 0), dir, straight: 0 });
+
+    let neighbours = |&State{ pos, dir, straight }: &State| {
+        let (allowed_turn, allowed_straight) = allowed_dirs(straight);
+
+        let mut next = Vec::new();
+        if allowed_turn {
+            next.push((dir.turn_right(), 0));
+            next.push((dir.turn_left(), 0));
+        }
+        if allowed_straight {
+            next.push((dir, straight + 1));
+        }
+
+        return next.into_iter().filter_map(move |(dir, straight)| {
+            let pos = map.step(pos, dir.delta())?;
+            return Some((State{ pos, dir, straight }, map[pos] as u32));
+        });
+    };
+
+    let is_goal = |s: &State| s.pos == goal && allowed_dirs(s.straight).0;
+
+    return graph::dijkstra(starts, neighbours, is_goal);
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u32 = 17;
+    type Input = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let map = Grid::parse_with(input, input, "0123456789", |v| v.to_digit(10).unwrap() as u8)?;
+        return Ok(map);
+    }
+
+    fn part1(map: &Self::Input) -> Result<Answer> {
+        let path = explore(map, allowed_dirs_part1).ok_or("can't reach the factory")?;
+        return Ok(path.cost.into());
+    }
+
+    fn part2(map: &Self::Input) -> Result<Answer> {
+        let path = explore(map, allowed_dirs_part2).ok_or("can't reach the factory")?;
+        return Ok(path.cost.into());
+    }
+
+    // The route for each part over a heatmap of the heat loss
+    fn draw(map: &Self::Input) -> Result<Vec<Picture>> {
+        let parts: [fn(usize) -> (bool, bool); 2] = [allowed_dirs_part1, allowed_dirs_part2];
+
+        let mut frames = Vec::new();
+        for (part, allowed_dirs) in (1..).zip(parts) {
+            let path = explore(map, allowed_dirs).ok_or("can't reach the factory")?;
+            let cells: Vec<(usize, usize)> = path.nodes.iter().map(|s| s.pos).collect();
+
+            let mut pic = Picture::new(map, |_, &v| (b'0' + v) as char);
+            pic.title(&format!("part {}, heat loss {}", part, path.cost))
+                .heatmap(map.iter().map(|(pos, &v)| (pos, v as u64)))
+                .path(&cells, picture::WHITE);
+            frames.push(pic);
+        }
+
+        return Ok(frames);
+    }
+}
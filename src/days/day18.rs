@@ -0,0 +1,84 @@
+use crate::direction::Direction;
+use crate::error::{self, ParseErr};
+use crate::geometry::Vec2;
+use crate::solution::{Answer, Result, Solution};
+
+fn polygon_area(verts: &Vec<Vec2<i64>>) -> i64 {
+    // Shoelace formula
+    let twice: i64 = verts.windows(2).map(|w| w[0].cross(w[1])).sum();
+    return twice / 2;
+}
+
+fn polygon_perimeter(verts: &Vec<Vec2<i64>>) -> i64 {
+    return verts.windows(2).map(|w| w[0].manhattan(w[1])).sum();
+}
+
+fn lagoon_size(verts: &Vec<Vec2<i64>>) -> i64 {
+    let area = polygon_area(verts);
+    let perimeter = polygon_perimeter(verts);
+    return area + perimeter / 2 + 1;
+}
+
+pub struct DigPlan {
+    p1_verts: Vec<Vec2<i64>>,
+    p2_verts: Vec<Vec2<i64>>,
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u32 = 18;
+    type Input = DigPlan;
+
+    fn parse(input: &str) -> Result<DigPlan> {
+        let mut p1_verts = Vec::new();
+        let mut p2_verts = Vec::new();
+
+        p1_verts.push(Vec2::new(0, 0));
+        p2_verts.push(Vec2::new(0, 0));
+
+        for line in input.lines() {
+            let parts: Vec<_> = line.split(" ").collect();
+            if parts.len() != 3 {
+                return Err(ParseErr::at(input, line, "expected 'DIR N (#COLOUR)'").into());
+            }
+
+            { // Part 1
+                let dp = parts[0].parse::<Direction>().ok()
+                    .filter(|_| "UDLR".contains(parts[0]))
+                    .ok_or_else(|| ParseErr::at(input, parts[0], "expected U, D, L or R"))?
+                    .delta();
+                let n: i64 = error::number(input, parts[1])?;
+                let last = *p1_verts.last().unwrap();
+
+                p1_verts.push(last + Vec2::new(dp.0 as i64, dp.1 as i64) * n);
+            }
+
+            { // Part 2
+                let colour = parts[2];
+                let (n, dir) = colour.strip_prefix("(#")
+                    .and_then(|c| c.strip_suffix(")"))
+                    .filter(|c| c.len() == 6 && c.is_ascii())
+                    .and_then(|c| Some((
+                        i64::from_str_radix(&c[..5], 16).ok()?,
+                        Direction::from_hex(c.chars().last()?)?,
+                    )))
+                    .ok_or_else(|| ParseErr::at(input, colour, "expected '(#XXXXXD)' with D from 0 to 3"))?;
+                let dp = dir.delta();
+                let last = *p2_verts.last().unwrap();
+
+                p2_verts.push(last + Vec2::new(dp.0 as i64, dp.1 as i64) * n);
+            }
+        }
+
+        return Ok(DigPlan{ p1_verts, p2_verts });
+    }
+
+    fn part1(plan: &DigPlan) -> Result<Answer> {
+        return Ok(lagoon_size(&plan.p1_verts).into());
+    }
+
+    fn part2(plan: &DigPlan) -> Result<Answer> {
+        return Ok(lagoon_size(&plan.p2_verts).into());
+    }
+}
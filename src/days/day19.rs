@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use crate::error::ParseErr;
+use crate::interval::Hyperrectangle;
+use crate::solution::{self, Answer, Solution};
+
+#[derive(Debug, Clone)]
+enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+impl FromStr for Category {
+    type Err = ParseErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "x" => { Ok(Category::X) },
+            "m" => { Ok(Category::M) },
+            "a" => { Ok(Category::A) },
+            "s" => { Ok(Category::S) },
+            _ => Err(ParseErr::new(s, "expected x, m, a or s")),
+        }
+    }
+}
+
+impl Category {
+    // Which dimension of a RangePart this is
+    fn index(&self) -> usize {
+        match self {
+            Category::X => 0,
+            Category::M => 1,
+            Category::A => 2,
+            Category::S => 3,
+        }
+    }
+}
+
+// Every combination of ratings in some ranges, one dimension per category
+type RangePart = Hyperrectangle<u32, 4>;
+
+#[derive(Debug, Clone)]
+struct Part {
+    x: u32,
+    m: u32,
+    a: u32,
+    s: u32,
+}
+
+impl FromStr for Part {
+    type Err = ParseErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s.strip_prefix("{")
+            .and_then(|s| s.strip_suffix("}"))
+            .ok_or(ParseErr::new(s, "expected '{x=N,m=N,a=N,s=N}'"))?;
+
+        let mut part = Part{ x: 0, m: 0, a: 0, s: 0 };
+
+        for value in values.split(",") {
+            let (name, num) = value.split_once("=").ok_or(ParseErr::new(value, "expected '='"))?;
+            let num = num.parse::<u32>().or(Err(ParseErr::new(num, "expected a number")))?;
+            match name {
+                "x" => { part.x = num },
+                "m" => { part.m = num },
+                "a" => { part.a = num },
+                "s" => { part.s = num },
+                _ => return Err(ParseErr::new(name, "expected x, m, a or s")),
+            }
+        }
+
+        return Ok(part);
+    }
+}
+
+impl Part {
+    fn get(&self, category: Category) -> u32 {
+        match category {
+            Category::X => { return self.x },
+            Category::M => { return self.m },
+            Category::A => { return self.a },
+            Category::S => { return self.s },
+        }
+    }
+
+    fn value(&self) -> u32 {
+        return self.x + self.m + self.a + self.s;
+    }
+}
+
+#[derive(Debug, Clone)]
+enum RuleType {
+    Lt,
+    Gt,
+    Always,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    category: Option<Category>,
+    value: u32,
+    target: String,
+    rule_type: RuleType,
+}
+
+impl Rule {
+    fn check_lt(&self, part: &Part) -> bool {
+        return part.get(self.category.as_ref().unwrap().clone()) < self.value;
+    }
+
+    fn check_gt(&self, part: &Part) -> bool {
+        return part.get(self.category.as_ref().unwrap().clone()) > self.value;
+    }
+
+    fn check(&self, part: &Part) -> bool {
+        match self.rule_type {
+            RuleType::Lt => return self.check_lt(part),
+            RuleType::Gt => return self.check_gt(part),
+            RuleType::Always => return true,
+        }
+    }
+
+    fn parse_cmp(cmp: &str, target: &str) -> Result<Self, <Rule as FromStr>::Err> {
+        let bad = || ParseErr::new(cmp, "expected a comparison like 'x<10'");
+        let cat = cmp.get(0..1).ok_or_else(bad)?;
+        let op = cmp.get(1..2).ok_or_else(bad)?;
+        let val = cmp.get(2..).ok_or_else(bad)?;
+        let val = &val.parse::<u32>().or(Err(ParseErr::new(val, "expected a number")))?;
+
+        let category = Category::from_str(cat)?;
+        match op {
+            "<" => {
+                Ok(Rule{
+                    category: Some(category),
+                    value: *val,
+                    target: target.to_string(),
+                    rule_type: RuleType::Lt,
+                })
+            },
+            ">" => {
+                Ok(Rule{
+                    category: Some(category),
+                    value: *val,
+                    target: target.to_string(),
+                    rule_type: RuleType::Gt,
+                })
+            },
+            _ => Err(ParseErr::new(op, "expected '<' or '>'")),
+        }
+    }
+
+    fn check_range(&self, part: &RangePart) -> (RangePart, RangePart) {
+        if let Some(category) = &self.category {
+            let dim = category.index();
+
+            match self.rule_type {
+                RuleType::Lt => {
+                    return part.split_at(dim, self.value);
+                },
+                RuleType::Gt => {
+                    let (fail, pass) = part.split_at(dim, self.value.saturating_add(1));
+                    return (pass, fail);
+                },
+                _ => unreachable!(),
+            }
+        }
+
+        return (part.clone(), RangePart::empty());
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ParseErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((cmp, target)) => {
+                Self::parse_cmp(cmp, target)
+            },
+            None => {
+                Ok(Rule{
+                    category: None,
+                    value: 0,
+                    target: s.to_string(),
+                    rule_type: RuleType::Always,
+                })
+            },
+        }
+    }
+}
+
+// Where a rule sends parts, with the workflows by their index
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Target {
+    Accept,
+    Reject,
+    Workflow(usize),
+}
+
+#[derive(Debug, Clone)]
+struct Workflow {
+    name: String,
+    rules: Vec<Rule>,
+    // Where each rule goes, once all the workflows have been read
+    next: Vec<Target>,
+}
+
+impl FromStr for Workflow {
+    type Err = ParseErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, rest) = s.split_once("{").ok_or(ParseErr::new(s, "expected '{'"))?;
+        let rest = rest.strip_suffix("}").ok_or(ParseErr::new(rest, "expected '}' at the end"))?;
+
+        let rs: Vec<&str> = rest.split(",").collect();
+        let rules = rs.iter().map(|s| Rule::from_str(s)).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Workflow{
+            name: name.to_string(),
+            rules,
+            next: Vec::new(),
+        })
+    }
+}
+
+pub struct System {
+    workflows: Vec<Workflow>,
+    start: usize,
+    parts: Vec<Part>,
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u32 = 19;
+    type Input = System;
+
+    fn parse(input: &str) -> solution::Result<System> {
+        let mut lines = input.lines();
+
+        let mut workflows: Vec<Workflow> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut wf_lines = Vec::new();
+
+        for line in lines.by_ref() {
+            if line.is_empty() {
+                break
+            }
+
+            let wf = Workflow::from_str(line).map_err(|e| e.within(input, line))?;
+            index.insert(wf.name.to_string(), workflows.len());
+            workflows.push(wf);
+            wf_lines.push(line);
+        }
+
+        // Everything has to lead to somewhere, starting from "in"
+        let start = *index.get("in")
+            .ok_or_else(|| ParseErr::end_of(input, input, "expected an 'in' workflow"))?;
+        for (wf, line) in workflows.iter_mut().zip(wf_lines) {
+            // Or the parts which don't match anything have nowhere to go
+            if !wf.rules.last().is_some_and(|r| matches!(r.rule_type, RuleType::Always)) {
+                return Err(ParseErr::end_of(input, line, "expected a rule without a condition at the end").into());
+            }
+            for rule in &wf.rules {
+                let target = match rule.target.as_str() {
+                    "A" => Target::Accept,
+                    "R" => Target::Reject,
+                    name => Target::Workflow(*index.get(name)
+                        .ok_or_else(|| ParseErr::new(name, "unknown workflow").within(input, line))?),
+                };
+                wf.next.push(target);
+            }
+        }
+
+        let mut parts = Vec::new();
+
+        for line in lines {
+            parts.push(Part::from_str(line).map_err(|e| e.within(input, line))?);
+        }
+
+        return Ok(System{ workflows, start, parts });
+    }
+
+    fn part1(system: &System) -> solution::Result<Answer> {
+        let workflows = &system.workflows;
+
+        let mut total = 0;
+
+        for part in &system.parts {
+            let mut at = Target::Workflow(system.start);
+            while let Target::Workflow(i) = at {
+                let wf = &workflows[i];
+                for (rule, next) in wf.rules.iter().zip(&wf.next) {
+                    if rule.check(part) {
+                        at = *next;
+                        break;
+                    }
+                }
+            }
+
+            if at == Target::Accept {
+                total += part.value();
+            }
+        }
+
+        return Ok(total.into());
+    }
+
+    fn part2(system: &System) -> solution::Result<Answer> {
+        let workflows = &system.workflows;
+
+        let mut live = Vec::new();
+        live.push((
+            system.start,
+            RangePart::new([1..4001, 1..4001, 1..4001, 1..4001]),
+        ));
+
+        let mut accept = Vec::new();
+
+        while let Some((i, range)) = live.pop() {
+            let wf = &workflows[i];
+            let mut range = range;
+            for (rule, next) in wf.rules.iter().zip(&wf.next) {
+                let (pass, fail) = rule.check_range(&range);
+
+                if !pass.is_empty() {
+                    match next {
+                        Target::Accept => { accept.push(pass); },
+                        Target::Reject => { },
+                        Target::Workflow(j) =>  { live.push((*j, pass)); },
+                    }
+                }
+
+                range = fail;
+            }
+        }
+
+        let total: u128 = accept.iter().map(|a| a.volume()).sum();
+        return Ok(u64::try_from(total)?.into());
+    }
+}
@@ -0,0 +1,338 @@
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+use regex::Regex;
+use crate::dot::Dot;
+use crate::error::{self, ParseErr};
+use crate::graph;
+use crate::numtheory::{crt, first_at_least};
+use crate::solution::{self, Answer, Solution};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum ModuleKind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    Unknown,
+}
+
+impl FromStr for ModuleKind {
+    type Err = ParseErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => { Ok(ModuleKind::Broadcaster) },
+            "%" => { Ok(ModuleKind::FlipFlop) },
+            "&" => { Ok(ModuleKind::Conjunction) },
+            _ => Err(ParseErr::new(s, "expected '%', '&' or nothing")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone)]
+pub struct Module {
+    inputs: HashMap<String, Pulse>,
+    outputs: Vec<String>,
+    kind: ModuleKind,
+    state: bool,
+}
+
+impl Default for Module {
+        fn default() -> Self {
+            return Self {
+                inputs: HashMap::new(),
+                outputs: Vec::new(),
+                kind: ModuleKind::Unknown,
+                state: false,
+            };
+        }
+}
+
+impl Module {
+    fn set_kind(&mut self, kind: ModuleKind) {
+        self.kind = kind;
+    }
+
+    fn add_input(&mut self, ip: &str) {
+        self.inputs.insert(ip.to_string(), Pulse::Low);
+    }
+
+    fn add_output(&mut self, op: &str) {
+        self.outputs.push(op.to_string());
+    }
+
+    fn __send_pulse(&self, pulse: Pulse) -> Vec<(String, Pulse)> {
+        return Vec::from_iter(self.outputs.iter().map(|name| (name.to_string(), pulse)));
+    }
+
+    fn receive_pulse(&mut self, from: &str, pulse: Pulse) -> Option<Vec<(String, Pulse)>> {
+        self.inputs.insert(from.to_string(), pulse);
+
+        match self.kind {
+            ModuleKind::Broadcaster => {
+                Some(self.__send_pulse(pulse))
+            },
+            ModuleKind::FlipFlop => {
+                match pulse {
+                    Pulse::Low => {
+                        self.state = !self.state;
+                        Some(self.__send_pulse(if self.state { Pulse::High } else { Pulse::Low }))
+                    },
+                    Pulse::High => {
+                        None
+                    },
+                }
+            },
+            ModuleKind::Conjunction => {
+                if self.inputs.values().all(|&v| v == Pulse::High) {
+                    Some(self.__send_pulse(Pulse::Low))
+                } else {
+                    Some(self.__send_pulse(Pulse::High))
+                }
+            },
+            ModuleKind::Unknown => { None },
+        }
+    }
+}
+
+// Everything the modules remember, in a fixed order, to tell when they're
+// back where they were
+fn snapshot(modules: &HashMap<String, Module>) -> Vec<bool> {
+    let mut names: Vec<&String> = modules.keys().collect();
+    names.sort();
+
+    let mut bits = Vec::new();
+    for name in names {
+        let module = &modules[name];
+        bits.push(module.state);
+
+        let mut inputs: Vec<(&String, &Pulse)> = module.inputs.iter().collect();
+        inputs.sort_by_key(|&(name, _)| name);
+        bits.extend(inputs.iter().map(|(_, &p)| p == Pulse::High));
+    }
+
+    return bits;
+}
+
+fn draw(modules: &HashMap<String, Module>) -> Dot {
+    let mut dot = Dot::digraph();
+
+    let mut names: Vec<&String> = modules.keys().collect();
+    names.sort();
+
+    // The broadcaster usually starts several separate counters, which only
+    // meet again at the end, so put each one in a box
+    if let Some(broadcaster) = modules.get("broadcaster") {
+        let reach: Vec<HashMap<&String, usize>> = broadcaster.outputs.iter()
+            .map(|start| graph::bfs([start], |name: &&String| {
+                return modules.get(*name).into_iter().flat_map(|m| m.outputs.iter());
+            }).dist)
+            .collect();
+
+        for (i, start) in broadcaster.outputs.iter().enumerate() {
+            let only_here: Vec<&&String> = names.iter()
+                .filter(|name| reach.iter().enumerate().all(|(j, r)| r.contains_key(**name) == (i == j)))
+                .collect();
+            if !only_here.is_empty() {
+                dot.cluster(start, only_here);
+            }
+        }
+    }
+
+    for name in &names {
+        let module = &modules[*name];
+        let node = dot.node(name).label(&format!("{}\n{:?}", name, module.kind));
+        match module.kind {
+            ModuleKind::FlipFlop => { node.shape("box"); },
+            ModuleKind::Conjunction => { node.shape("diamond"); },
+            // The broadcaster and the outputs (like rx)
+            _ => { node.highlight(); },
+        }
+
+        for target in &module.outputs {
+            dot.edge(name, target);
+        }
+    }
+
+    return dot;
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u32 = 20;
+    type Input = HashMap<String, Module>;
+
+    fn parse(input: &str) -> solution::Result<Self::Input> {
+        let module_re = Regex::new(r"^([%&]?)([a-zA-Z]+)$").unwrap();
+
+        let mut modules: HashMap<String, Module> = HashMap::new();
+
+        for line in input.lines() {
+            let (left, right) = error::split(input, line, " -> ")?;
+
+            let caps = module_re.captures(left)
+                .ok_or_else(|| ParseErr::at(input, left, "expected a module name"))?;
+            let kind = caps.get(1).unwrap().as_str();
+            let module_kind = ModuleKind::from_str(kind).map_err(|e| e.within(input, line))?;
+            let module_name = caps.get(2).unwrap().as_str();
+
+            let module = modules.entry(module_name.to_string()).or_default();
+            module.set_kind(module_kind);
+
+            let outputs: Vec<&str> = right.split(", ").collect();
+            for output in &outputs {
+                module.add_output(output);
+            }
+
+            for output in &outputs {
+                let op_module = modules.entry(output.to_string()).or_default();
+                op_module.add_input(module_name);
+            }
+        }
+
+        if !modules.contains_key("broadcaster") {
+            return Err(ParseErr::end_of(input, input, "expected a 'broadcaster' module").into());
+        }
+
+        return Ok(modules);
+    }
+
+    fn part1(modules: &Self::Input) -> solution::Result<Answer> {
+        let mut modules = modules.clone();
+
+        let mut pulses: VecDeque<(String, String, Pulse)> = VecDeque::new();
+
+        let mut high_pulses: u64 = 0;
+        let mut low_pulses: u64 = 0;
+
+        let num_buttons = 1000;
+        for _ in 0..num_buttons {
+            pulses.push_back(("button".to_string(), "broadcaster".to_string(), Pulse::Low));
+
+            while let Some((source, target, pulse)) = pulses.pop_front() {
+                //eprintln!("{} -{:?}-> {}", source, pulse, target);
+
+                match pulse {
+                    Pulse::High => high_pulses += 1,
+                    Pulse::Low => low_pulses += 1,
+                }
+
+                let module = modules.get_mut(&target).ok_or_else(|| format!("no module {}", target))?;
+                if let Some(new_pulses) = module.receive_pulse(&source, pulse) {
+                    for (new_target, new_pulse) in new_pulses {
+                        pulses.push_back((target.clone(), new_target, new_pulse));
+                    }
+                }
+            }
+        }
+
+        return Ok((low_pulses * high_pulses).into());
+    }
+
+    fn part2(modules: &Self::Input) -> solution::Result<Answer> {
+        // Start over
+        let mut modules = modules.clone();
+
+        let mut pulses: VecDeque<(String, String, Pulse)> = VecDeque::new();
+
+        // Find who feeds "rx"
+        let rx_inputs = &modules.get("rx").ok_or("no rx module")?.inputs;
+        if rx_inputs.len() != 1 {
+            return Err(format!("rx has {} inputs, expected 1", rx_inputs.len()).into());
+        }
+
+        let rx_input = rx_inputs.keys().collect::<Vec<&String>>()[0].clone();
+
+        let final_module = modules.get(&rx_input).ok_or_else(|| format!("no module {}", rx_input))?;
+        if final_module.kind != ModuleKind::Conjunction {
+            return Err(format!("{} feeds rx, but it isn't a conjunction", rx_input).into());
+        }
+
+        // It's a Conjunction, so we need to track the high pulses arriving
+        // on its input
+        let mut high_pulses: HashMap<String, Vec<u32>> = HashMap::from_iter(
+            final_module.inputs.keys().map(|v| (v.clone(), vec![0]))
+        );
+
+        // When the modules get back to a state they've been in, they'll go
+        // round the same presses forever, so any input which is going to go
+        // high 3 times will have done within 3 more times round
+        let mut seen: HashMap<Vec<bool>, u32> = HashMap::new();
+        seen.insert(snapshot(&modules), 0);
+        let mut limit: Option<u32> = None;
+
+        let mut num_buttons = 0;
+        'done: loop {
+            pulses.push_back(("button".to_string(), "broadcaster".to_string(), Pulse::Low));
+            num_buttons += 1;
+
+            while let Some((source, target, pulse)) = pulses.pop_front() {
+                if pulse == Pulse::High && target == rx_input {
+                    // Only once per press, even if it pulses more than once
+                    if let Some(highs) = high_pulses.get_mut(&source) {
+                        if highs.last() != Some(&num_buttons) {
+                            highs.push(num_buttons);
+                        }
+                    }
+                }
+
+                let module = modules.get_mut(&target).ok_or_else(|| format!("no module {}", target))?;
+                if let Some(new_pulses) = module.receive_pulse(&source, pulse) {
+                    for (new_target, new_pulse) in new_pulses {
+                        pulses.push_back((target.clone(), new_target, new_pulse));
+                    }
+                }
+            }
+
+            // Wait until we've seen 3 high pulses from each input
+            if high_pulses.values().all(|v| v.len() > 3) {
+                break 'done;
+            }
+
+            if limit.is_some_and(|l| num_buttons >= l) {
+                let mut never: Vec<&String> = high_pulses.iter()
+                    .filter(|(_, v)| v.len() <= 3)
+                    .map(|(input, _)| input)
+                    .collect();
+                never.sort();
+                return Err(format!("{} never goes high 3 times", never[0]).into());
+            }
+            if limit.is_none() {
+                let state = snapshot(&modules);
+                if let Some(&before) = seen.get(&state) {
+                    limit = Some(num_buttons + 3 * (num_buttons - before));
+                } else {
+                    seen.insert(state, num_buttons);
+                }
+            }
+        }
+
+        // Each input first goes high after some number of presses, and then
+        // every so many after that. Find when they all line up.
+        let mut congruences = Vec::new();
+        let mut first = 0;
+        for (input, v) in &high_pulses {
+            let offset = v[1];
+            let period = v[2] - v[1];
+            if (3..v.len()).any(|i| v[i] - v[i - 1] != period) {
+                return Err(format!("{} doesn't go high at a regular interval", input).into());
+            }
+
+            congruences.push((offset as i128, period as i128));
+            first = first.max(offset);
+        }
+
+        let (r, m) = crt(&congruences).ok_or("the inputs never all go high together")?;
+        return Ok((first_at_least(r, m, first as i128) as u64).into());
+    }
+
+    fn dot(modules: &Self::Input) -> Option<Dot> {
+        return Some(draw(modules));
+    }
+}
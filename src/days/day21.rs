@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use crate::direction::Direction;
+use crate::error::ParseErr;
+use crate::graph;
+use crate::grid::Grid;
+use crate::picture::{self, Picture};
+use crate::solution::{Answer, Result, Solution};
+
+const NTILES: usize = 9;
+
+fn move_in_dir(
+    garden: &Garden,
+    from: &(i32, i32),
+    dir: Direction
+) -> Option<(i32, i32)> {
+    let dp = dir.delta();
+    let nx = from.0 + dp.0;
+    let ny = from.1 + dp.1;
+
+    if nx < garden.x_bounds.0 || nx > garden.x_bounds.1 ||
+        ny < garden.y_bounds.0 || ny > garden.y_bounds.1 {
+        return None;
+    }
+
+    let c = garden.lookup_infinite(&(nx, ny));
+
+    if c == '#' {
+        return None;
+    }
+
+    return Some((nx, ny));
+}
+
+// Starts from the squares one step away, as standing still isn't a step, so
+// the start itself is only reachable (in 2) if there's somewhere to go
+fn build_min_distance(
+    garden: &Garden,
+    start: &(i32, i32),
+) -> HashMap<(i32, i32), usize> {
+    let neighbours = |pos: &(i32, i32)| {
+        let pos = *pos;
+        return Direction::iter().filter_map(move |dir| move_in_dir(garden, &pos, dir));
+    };
+
+    let seen = graph::bfs(neighbours(start), neighbours);
+    return seen.dist.into_iter().map(|(pos, d)| (pos, d + 1)).collect();
+}
+
+pub struct Garden {
+    grid: Grid<char>,
+    origin: (usize, usize),
+    x_bounds: (i32, i32),
+    y_bounds: (i32, i32),
+}
+
+impl Garden {
+    fn new(grid: Grid<char>, origin: (usize, usize)) -> Garden {
+        let cols = grid.width();
+        let rows = grid.height();
+
+        let total_x = cols * NTILES;
+        let total_y = rows * NTILES;
+
+        let minx = -((total_x / 2) as i32);
+        let maxx = (total_x / 2) as i32;
+        let miny = -((total_y / 2) as i32);
+        let maxy = (total_y / 2) as i32;
+
+        return Garden{
+            grid,
+            origin,
+            x_bounds: (minx, maxx),
+            y_bounds: (miny, maxy),
+        };
+    }
+
+    fn lookup_infinite(&self, coord: &(i32, i32)) -> char {
+        let offset = (coord.0 + self.origin.0 as i32, coord.1 + self.origin.1 as i32);
+        return *self.grid.get_wrapping(offset);
+    }
+}
+
+// The whole area part 1 searches, first with the distances to each square as
+// a heatmap, then with the ones reachable in `steps` marked
+fn draw_distances(garden: &Garden, min_distance: &HashMap<(i32, i32), usize>, steps: usize) -> Vec<Picture> {
+    let width = (garden.x_bounds.1 - garden.x_bounds.0 + 1) as usize;
+    let height = (garden.y_bounds.1 - garden.y_bounds.0 + 1) as usize;
+    let to_grid = |(x, y): (i32, i32)| ((x - garden.x_bounds.0) as usize, (y - garden.y_bounds.0) as usize);
+
+    let mut tiles = Grid::new(width, height, '.');
+    for y in garden.y_bounds.0..=garden.y_bounds.1 {
+        for x in garden.x_bounds.0..=garden.x_bounds.1 {
+            tiles[to_grid((x, y))] = garden.lookup_infinite(&(x, y));
+        }
+    }
+    let start = to_grid((0, 0));
+
+    let mut distances = Picture::new(&tiles, |_, &c| c);
+    distances.title("distance from the start")
+        .heatmap(min_distance.iter().map(|(&pos, &d)| (to_grid(pos), d as u64)))
+        .colour([start], picture::RED);
+
+    let reachable = min_distance.iter()
+        .filter(|&(_, &d)| d <= steps && (d & 1) == (steps & 1))
+        .map(|(&pos, _)| to_grid(pos));
+
+    let mut reached = Picture::new(&tiles, |_, &c| c);
+    reached.title(&format!("{} reachable in {} steps", count_reachable(min_distance, steps), steps))
+        .mark(reachable, 'O', picture::GREEN)
+        .colour([start], picture::RED);
+
+    return vec![distances, reached];
+}
+
+// A square is reachable if its min distance is less than the number of
+// steps, and also has the same "LSB" as the number of steps.
+// For example, with an "even" number of steps, we can't reach any tiles
+// with "odd" distances, because we'd need to take two detours to get to
+// them.
+fn count_reachable(min_distance: &HashMap<(i32, i32), usize>, n: usize) -> usize {
+    let mut reachable = 0;
+    for v in min_distance.values() {
+        if *v <= n && (*v & 1) == (n & 1) {
+            reachable += 1;
+        }
+    }
+
+    return reachable;
+}
+
+pub fn reachable(garden: &Garden, steps: usize) -> usize {
+    let min_distance = build_min_distance(garden, &(0, 0));
+    return count_reachable(&min_distance, steps);
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u32 = 21;
+    type Input = Garden;
+
+    fn parse(input: &str) -> Result<Garden> {
+        let grid = Grid::parse(input, input, ".#S")?;
+        let start = grid.find(&'S')
+            .ok_or_else(|| ParseErr::end_of(input, input, "expected a start 'S'"))?;
+
+        return Ok(Garden::new(grid, start));
+    }
+
+    fn part1(garden: &Garden) -> Result<Answer> {
+        let min_distance = build_min_distance(garden, &(0, 0));
+        return Ok(count_reachable(&min_distance, 64).into());
+    }
+
+    fn part2(_garden: &Garden) -> Result<Answer> {
+        return Ok(Answer::Unsolved);
+    }
+
+    fn draw(garden: &Garden) -> Result<Vec<Picture>> {
+        let min_distance = build_min_distance(garden, &(0, 0));
+        return Ok(draw_distances(garden, &min_distance, 64));
+    }
+}
+
+/* Thoughts:
+ *
+ * - We can probably work out how "wide" the solid area is
+ *    num blocks: 26M - (block(1,0) top left) / block_width
+ * - Then we can work out the top-left of that block
+ *    - Need a function which we give block coords, and it gives distance to 4 corners(?)
+ *    - Confirm that this block has corners <=goal and >=goal
+ *      NOTE: Corners not enough to be sure it's complete. Row y=0 is "weird"
+ * - Then we know there's a strip of blocks up to this of solid
+ * - How many stragglers?
+ *    - Need a function which fills out block, given corners
+ *       - First fill edges based on relative position to origin?
+ *       - Then fill from minimum coord?
+ */
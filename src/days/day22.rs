@@ -0,0 +1,172 @@
+use std::collections::{BTreeMap, HashSet};
+use std::str::FromStr;
+use std::cmp::Ordering;
+use crate::error;
+use crate::geometry::{self, Aabb, Vec2};
+use crate::solution::{Answer, Result, Solution};
+
+type Vec3 = geometry::Vec3<u32>;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Brick {
+    id: u32,
+    start: Vec3,
+    end: Vec3,
+}
+
+impl Brick {
+    fn new(id: u32, a: Vec3, b: Vec3) -> Brick {
+        return Brick{
+            id,
+            start: std::cmp::min(a, b),
+            end: std::cmp::max(a, b),
+        }
+    }
+
+    // The area it covers, looking down from above
+    fn footprint(&self) -> Aabb<Vec2<u32>> {
+        return Aabb::new(self.start.xy(), self.end.xy());
+    }
+
+    #[allow(dead_code)]
+    fn floating(&self, floor: &mut BTreeMap<(u32, u32), u32>) -> bool {
+        for y in self.start.y..=self.end.y {
+            for x in self.start.x..=self.end.x {
+                if self.start.z - 1 <= *floor.entry((x, y)).or_insert(0) {
+                    return false;
+                }
+            }
+        }
+
+        return true;
+    }
+
+    fn drop(&mut self, floor: &mut BTreeMap<(u32, u32), u32>) -> bool {
+        let mut max_z = 0;
+        for y in self.start.y..=self.end.y {
+            for x in self.start.x..=self.end.x {
+                let z = *floor.entry((x, y)).or_insert(0);
+                max_z = std::cmp::max(max_z, z);
+            }
+        }
+
+        let new_z = max_z + 1;
+        let changed = new_z != self.start.z;
+
+        let z_offs = self.start.z - new_z;
+        self.start.z = new_z;
+        self.end.z -= z_offs;
+
+        for y in self.start.y..=self.end.y {
+            for x in self.start.x..=self.end.x {
+                floor.insert((x, y), self.end.z);
+            }
+        }
+
+        return changed;
+    }
+}
+
+// The bricks each one is resting on, by where they are in `bricks`, which
+// must have settled and be sorted
+fn supporters(bricks: &Vec<Brick>) -> Vec<Vec<usize>> {
+    let mut supporters = Vec::new();
+    for (i, b) in bricks.iter().enumerate() {
+        let mut below = Vec::new();
+        for (j, other) in bricks[..i].iter().enumerate() {
+            if other.end.z == b.start.z - 1 && other.footprint().intersects(&b.footprint()) {
+                below.push(j);
+            }
+        }
+        supporters.push(below);
+    }
+
+    return supporters;
+}
+
+impl Ord for Brick {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start.cmp(&other.start)
+    }
+}
+
+impl PartialOrd for Brick {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u32 = 22;
+
+    // The bricks, after they've all settled
+    type Input = Vec<Brick>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut bricks = Vec::new();
+
+        for line in input.lines() {
+            let (a, b) = error::split(input, line, "~")?;
+
+            let a = Vec3::from_str(a).map_err(|e| e.within(input, a))?;
+            let b = Vec3::from_str(b).map_err(|e| e.within(input, b))?;
+
+            let brick  = Brick::new(bricks.len() as u32, a, b);
+            bricks.push(brick);
+        }
+
+        // Sort by min Z
+        bricks.sort();
+
+        // Keep track of the "floor" as we drop bricks
+        let mut floor: BTreeMap<(u32, u32), u32> = BTreeMap::new();
+
+        // Drop them all
+        for brick in bricks.iter_mut() {
+            brick.drop(&mut floor);
+        }
+
+        // Re-sort in their settled positions
+        bricks.sort();
+
+        return Ok(bricks);
+    }
+
+    fn part1(bricks: &Self::Input) -> Result<Answer> {
+        // All bricks are candidates for disintegration, except the ones which
+        // are the only thing holding another brick up
+        let mut candidates: HashSet<usize> = HashSet::from_iter(0..bricks.len());
+        for below in supporters(bricks) {
+            if below.len() == 1 {
+                candidates.remove(&below[0]);
+            }
+        }
+
+        return Ok(candidates.len().into());
+    }
+
+    fn part2(bricks: &Self::Input) -> Result<Answer> {
+        let supporters = supporters(bricks);
+
+        // Disintegrating a brick drops everything resting only on falling
+        // bricks, and as they're sorted bottom up, whether each one falls is
+        // known by the time it's reached
+        let mut total = 0;
+        for i in 0..bricks.len() {
+            let mut falling = vec![false; bricks.len()];
+            falling[i] = true;
+
+            for j in i + 1..bricks.len() {
+                let below = &supporters[j];
+                if !below.is_empty() && below.iter().all(|&k| falling[k]) {
+                    falling[j] = true;
+                    total += 1;
+                }
+            }
+        }
+
+        return Ok(total.into());
+    }
+}
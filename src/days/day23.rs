@@ -0,0 +1,244 @@
+use std::collections::{BTreeSet, BTreeMap};
+use crate::direction::Direction;
+use crate::dot::Dot;
+use crate::error::ParseErr;
+use crate::grid::Grid;
+use crate::picture::{self, Picture};
+use crate::solution::{Answer, Result, Solution};
+
+fn move_in_dir(
+    map: &Grid<char>,
+    from: &(usize, usize),
+    dir: Direction,
+    part2: &bool
+) -> Option<(usize, usize)> {
+    let (nx, ny) = map.step(*from, dir.delta())?;
+
+    let cell = map[(nx, ny)];
+    match (part2, cell) {
+        (_, '#') => None,
+        // Can't go up a slope
+        (false, c) if Direction::from_arrow(c) == Some(dir.opposite()) => None,
+        _ => Some((nx, ny)),
+    }
+}
+
+// Squares (or just the junctions) in order along a trail
+type Route = Vec<(usize, usize)>;
+
+type Graph = BTreeMap<(usize, usize), BTreeSet<((usize, usize), usize)>>;
+
+fn insert_edge(graph: &mut Graph, a: &(usize, usize), b: &(usize, usize), distance: usize, bidir: &bool) {
+    graph.entry(*a)
+        .or_default()
+        .insert((*b, distance));
+    if *bidir {
+        graph.entry(*b)
+            .or_default()
+            .insert((*a, distance));
+    }
+}
+
+// Where the trail can go next from `current`, other than back to `prev`
+fn options(map: &Grid<char>, current: &(usize, usize), prev: &(usize, usize), part2: &bool) -> Vec<(usize, usize)> {
+    let cell = map[*current];
+    // Slopes can only be gone down
+    let dirs = match (part2, Direction::from_arrow(cell)) {
+        (false, Some(dir)) => vec![dir],
+        _ => Direction::ALL.to_vec(),
+    };
+
+    return dirs.iter()
+        .filter_map(
+            |d| move_in_dir(map, current, *d, part2)
+        )
+        .filter(|v| v != prev) // Can't go back - this only works if the path is 1-wide!
+        .collect();
+}
+
+// Follows the trail from `fork` through `from` up to the next junction or
+// dead end, returning the squares along it and where it could go from there
+fn follow(
+    map: &Grid<char>,
+    from: &(usize, usize),
+    fork: &(usize, usize),
+    part2: &bool,
+) -> (Route, Route) {
+    let mut trail = if *from == *fork { vec![*from] } else { vec![*fork, *from] };
+    let mut prev = *fork;
+
+    loop {
+        let current = *trail.last().unwrap();
+        let next = options(map, &current, &prev, part2);
+
+        if next.len() != 1 {
+            return (trail, next);
+        }
+
+        prev = current;
+        trail.push(next[0]);
+    }
+}
+
+fn build_graph(
+    map: &Grid<char>,
+    graph: &mut Graph,
+    from: &(usize, usize),
+    fork: &(usize, usize),
+    forks: &mut BTreeSet<(usize, usize)>,
+    part2: &bool) {
+
+    let (trail, next) = follow(map, from, fork, part2);
+    let current = *trail.last().unwrap();
+
+    insert_edge(graph, fork, &current, trail.len() - 1, part2);
+
+    if next.len() > 1 && !forks.contains(&current) {
+        forks.insert(current);
+        for option in next {
+            build_graph(map, graph, &option, &current, forks, part2);
+        }
+    }
+}
+
+// Tries every way to `goal` which doesn't go back through a junction already
+// on `route`, keeping the longest (and the junctions along it) in `best`
+fn explore_graph(
+    graph: &Graph,
+    from: &(usize, usize),
+    goal: &(usize, usize),
+    route: &mut Route,
+    distance: usize,
+    best: &mut Option<(usize, Route)>,
+) {
+    if let Some(options) = graph.get(from) {
+        for (option, dist) in options {
+            if route.contains(option) {
+                continue;
+            }
+
+            if option == goal {
+                if best.as_ref().is_none_or(|(longest, _)| distance + dist > *longest) {
+                    let mut route = route.clone();
+                    route.push(*goal);
+                    *best = Some((distance + dist, route));
+                }
+            } else {
+                route.push(*option);
+                explore_graph(graph, option, goal, route, distance + dist, best);
+                route.pop();
+            }
+        }
+    }
+}
+
+pub struct Trails {
+    map: Grid<char>,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+impl Trails {
+    // The junctions, and how far apart they are
+    fn graph(&self, part2: &bool) -> Graph {
+        let mut graph = Graph::new();
+        build_graph(&self.map, &mut graph, &self.start, &self.start, &mut BTreeSet::new(), part2);
+        return graph;
+    }
+
+    // How long it is, and the junctions it goes through
+    fn longest_hike(&self, part2: &bool) -> Option<(usize, Route)> {
+        let graph = self.graph(part2);
+
+        let mut best = None;
+        explore_graph(&graph, &self.start, &self.end, &mut vec![self.start], 0, &mut best);
+        return best;
+    }
+
+    // Fills in the squares between the junctions on `route`
+    fn squares(&self, route: &Route, part2: &bool) -> Route {
+        let mut squares = vec![self.start];
+
+        for leg in route.windows(2) {
+            let (from, to) = (leg[0], leg[1]);
+
+            // The longest trail from one junction to the next
+            let trail = options(&self.map, &from, &from, part2).iter()
+                .map(|option| follow(&self.map, option, &from, part2).0)
+                .filter(|trail| trail.last() == Some(&to))
+                .max_by_key(|trail| trail.len())
+                .unwrap();
+            squares.extend_from_slice(&trail[1..]);
+        }
+
+        return squares;
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+    type Input = Trails;
+
+    fn parse(input: &str) -> Result<Trails> {
+        let map = Grid::parse(input, input, ".#<>^v")?;
+
+        let first = input.lines().next()
+            .ok_or_else(|| ParseErr::end_of(input, input, "expected a map"))?;
+        let start_x = first.rfind('.')
+            .ok_or_else(|| ParseErr::at(input, first, "expected a path in the top row"))?;
+        let start = (start_x, 0);
+
+        let last = input.lines().last()
+            .ok_or_else(|| ParseErr::end_of(input, input, "expected a map"))?;
+        let end_x = last.rfind('.')
+            .ok_or_else(|| ParseErr::at(input, last, "expected a path in the bottom row"))?;
+        let end = (end_x, map.height() - 1);
+
+        return Ok(Trails{ map, start, end });
+    }
+
+    fn part1(trails: &Trails) -> Result<Answer> {
+        let (longest, _) = trails.longest_hike(&false).ok_or("no route to the end")?;
+        return Ok(longest.into());
+    }
+
+    fn part2(trails: &Trails) -> Result<Answer> {
+        let (longest, _) = trails.longest_hike(&true).ok_or("no route to the end")?;
+        return Ok(longest.into());
+    }
+
+    // The longest hike for each part, with the junctions it goes through
+    fn draw(trails: &Trails) -> Result<Vec<Picture>> {
+        let mut frames = Vec::new();
+        for (part, part2) in [(1, false), (2, true)] {
+            let (longest, route) = trails.longest_hike(&part2).ok_or("no route to the end")?;
+
+            let mut pic = Picture::new(&trails.map, |_, &c| c);
+            pic.title(&format!("part {}, {} steps", part, longest))
+                .path(&trails.squares(&route, &part2), picture::YELLOW)
+                .colour(route, picture::RED);
+            frames.push(pic);
+        }
+
+        return Ok(frames);
+    }
+
+    // The part 1 graph, where the slopes make it one way
+    fn dot(trails: &Trails) -> Option<Dot> {
+        let name = |p: &(usize, usize)| format!("{},{}", p.0, p.1);
+
+        let mut dot = Dot::digraph();
+        dot.node(&name(&trails.start)).label("start").highlight();
+        dot.node(&name(&trails.end)).label("end").highlight();
+
+        for (from, edges) in &trails.graph(&false) {
+            for (to, distance) in edges {
+                dot.edge(&name(from), &name(to)).weight(distance);
+            }
+        }
+
+        return Some(dot);
+    }
+}
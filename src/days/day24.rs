@@ -0,0 +1,128 @@
+use std::str::FromStr;
+use crate::error;
+use crate::geometry;
+use crate::solution::{Answer, Result, Solution};
+
+type Vec3 = geometry::Vec3<i64>;
+
+fn check_intersect(s1: &(Vec3, Vec3), s2: &(Vec3, Vec3)) -> Option<(f64, f64)> {
+    // Only the x and y matter, and the products need more than 64 bits
+    let p1 = s1.0.xy().widen();
+    let p2 = s1.1.xy().widen();
+    let p3 = s2.0.xy().widen();
+    let p4 = s2.1.xy().widen();
+
+    let t_num = (p1 - p3).cross(p3 - p4);
+    let u_num = (p1 - p3).cross(p1 - p2);
+    let den = (p1 - p2).cross(p3 - p4);
+
+    if den == 0 {
+        return None;
+    }
+
+    let t: f64 = t_num as f64 / den as f64;
+    if !(0.0..=1.0).contains(&t) {
+        return None;
+    }
+
+    let u: f64 = u_num as f64 / den as f64;
+    if u < 0.0 || t > 1.0 {
+        return None;
+    }
+
+    return Some((
+        p1.x as f64 + t * (p2.x as f64 - p1.x as f64),
+        p1.y as f64 + t * (p2.y as f64 - p1.y as f64),
+    ));
+}
+
+// Counts the pairs of hailstones whose paths cross inside the test area
+pub fn count_intersections(hailstones: &[(Vec3, Vec3)], region_min: i64, region_max: i64) -> usize {
+    let mut segments = Vec::new();
+
+    for &(pos, vel) in hailstones {
+        /*
+        if segments.len() < 10 {
+            println!("{}, {}, {}, {}, {}, {}",
+                     pos.x, pos.y, pos.z,
+                     pos.x + vel.x * 1000000000000,
+                     pos.y + vel.y * 1000000000000,
+                     pos.z + vel.z * 1000000000000);
+        }
+        */
+
+        let x_steps_to_min = (region_min - pos.x + (vel.x - vel.x.signum())) / vel.x;
+        let x_steps_to_max = (region_max - pos.x + (vel.x - vel.x.signum())) / vel.x;
+
+        let y_steps_to_min = (region_min - pos.y + (vel.y - vel.y.signum())) / vel.y;
+        let y_steps_to_max = (region_max - pos.y + (vel.y - vel.y.signum())) / vel.y;
+
+        let steps_entry = std::cmp::max(
+            std::cmp::min(x_steps_to_min, x_steps_to_max),
+            std::cmp::min(y_steps_to_min, y_steps_to_max),
+        );
+        let steps_exit = std::cmp::min(
+            std::cmp::max(x_steps_to_min, x_steps_to_max),
+            std::cmp::max(y_steps_to_min, y_steps_to_max),
+        );
+
+        let mut entry = pos + vel * steps_entry;
+        let exit = pos + vel * steps_exit;
+
+        if steps_entry < 0 && steps_exit < 0 {
+            continue;
+        } else if steps_entry < 0 {
+            entry = pos;
+        }
+
+        segments.push((entry, exit));
+    }
+
+    let mut count = 0;
+    for i in 0..segments.len() {
+        for j in i + 1..segments.len() {
+            if let Some(intersection) = check_intersect(&segments[i], &segments[j]) {
+                if intersection.0 >= region_min as f64 && intersection.0 <= region_max as f64 &&
+                    intersection.1 >= region_min as f64 && intersection.1 <= region_max as f64 {
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    return count;
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+
+    // (position, velocity) of each hailstone
+    type Input = Vec<(Vec3, Vec3)>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut hailstones = Vec::new();
+
+        for line in input.lines() {
+            let (pos, vel) = error::split(input, line, " @ ")?;
+            let pos = Vec3::from_str(pos).map_err(|e| e.within(input, pos))?;
+            let vel = Vec3::from_str(vel).map_err(|e| e.within(input, vel))?;
+
+            hailstones.push((pos, vel));
+        }
+
+        return Ok(hailstones);
+    }
+
+    fn part1(hailstones: &Self::Input) -> Result<Answer> {
+        let region_min = 200000000000000;
+        let region_max = 400000000000000;
+
+        return Ok(count_intersections(hailstones, region_min, region_max).into());
+    }
+
+    fn part2(_hailstones: &Self::Input) -> Result<Answer> {
+        return Ok(Answer::Unsolved);
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::{HashMap, HashSet};
+use crate::dot::Dot;
+use crate::error;
+use crate::graph;
+use crate::solution::{Answer, Result, Solution};
+
+type Graph = HashMap<String, HashSet<String>>;
+
+fn insert_edge(graph: &mut Graph, from: &str, to: &str) {
+    graph.entry(from.to_string())
+        .or_default()
+        .insert(to.to_string());
+    graph.entry(to.to_string())
+        .or_default()
+        .insert(from.to_string());
+}
+
+// Counts edge-disjoint paths from s to t, giving up once there are more than
+// `limit`. Also returns the nodes still reachable from s once they're used up.
+fn max_flow(adj: &Vec<Vec<usize>>, s: usize, t: usize, limit: usize) -> (usize, HashSet<usize>) {
+    let mut flow: HashMap<(usize, usize), i32> = HashMap::new();
+
+    let mut n_paths = 0;
+    loop {
+        let unused = |node: &usize| {
+            let node = *node;
+            return adj[node].iter()
+                .copied()
+                .filter(|&other| flow.get(&(node, other)).copied().unwrap_or(0) < 1)
+                .collect::<Vec<usize>>();
+        };
+        let reached = graph::bfs([s], unused);
+
+        let Some(path) = reached.path_to(&t) else {
+            return (n_paths, reached.dist.into_keys().collect());
+        };
+        if n_paths == limit {
+            return (n_paths + 1, reached.dist.into_keys().collect());
+        }
+
+        // Push one unit of flow back along the path
+        for w in path.windows(2) {
+            *flow.entry((w[0], w[1])).or_insert(0) += 1;
+            *flow.entry((w[1], w[0])).or_insert(0) -= 1;
+        }
+
+        n_paths += 1;
+    }
+}
+
+// One of the two groups left when cutting exactly three edges
+fn three_cut(graph: &Graph) -> Option<HashSet<&String>> {
+    let names: Vec<&String> = graph.keys().collect();
+    let index: HashMap<&String, usize> = names.iter()
+        .enumerate()
+        .map(|(i, name)| (*name, i))
+        .collect();
+
+    let adj: Vec<Vec<usize>> = names.iter()
+        .map(|name| graph[*name].iter().map(|other| index[other]).collect())
+        .collect();
+
+    for t in 1..adj.len() {
+        let (n_paths, left) = max_flow(&adj, 0, t, 3);
+        if n_paths == 3 {
+            return Some(left.iter().map(|&i| names[i]).collect());
+        }
+    }
+
+    return None;
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+    type Input = Graph;
+
+    fn parse(input: &str) -> Result<Graph> {
+        let mut graph = Graph::new();
+
+        for line in input.lines() {
+            let (a, rest) = error::split(input, line, ": ")?;
+
+            let bs = rest.split(" ");
+
+            for b in bs {
+                insert_edge(&mut graph, a, b);
+            }
+
+        }
+
+        return Ok(graph);
+    }
+
+    fn part1(graph: &Graph) -> Result<Answer> {
+        // I originally eyeballed the three edges from the .dot graph, but that
+        // only works for my input. Instead, find some node on the other side
+        // of a three edge cut from the first one and split there.
+        let left = three_cut(graph).ok_or("no three edge cut found")?;
+
+        return Ok((left.len() * (graph.len() - left.len())).into());
+    }
+
+    fn part2(_graph: &Graph) -> Result<Answer> {
+        // There is no part 2 on Christmas Day
+        return Ok(Answer::Unsolved);
+    }
+
+    // With the three edges to cut highlighted, if there are any
+    fn dot(graph: &Graph) -> Option<Dot> {
+        let mut dot = Dot::graph();
+        let left = three_cut(graph).unwrap_or_default();
+
+        let mut names: Vec<&String> = graph.keys().collect();
+        names.sort();
+
+        if !left.is_empty() {
+            let (l, r): (Vec<&String>, Vec<&String>) = names.iter().partition(|n| left.contains(*n));
+            dot.cluster("left", l);
+            dot.cluster("right", r);
+        }
+
+        for a in &names {
+            let mut others: Vec<&String> = graph[*a].iter().filter(|b| a < b).collect();
+            others.sort();
+            for b in others {
+                let edge = dot.edge(a, b);
+                if left.contains(a) != left.contains(b) {
+                    edge.highlight();
+                }
+            }
+        }
+
+        return Some(dot);
+    }
+}
@@ -0,0 +1,154 @@
+//! Moving around a 2D map.
+//!
+//! Directions are in clockwise order starting from East (with y pointing
+//! down), which is also the order the puzzles number them in.
+
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Direction {
+    East,
+    South,
+    West,
+    North,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::East, Direction::South, Direction::West, Direction::North];
+
+    pub fn iter() -> impl Iterator<Item = Direction> {
+        return Direction::ALL.into_iter();
+    }
+
+    fn index(self) -> usize {
+        return self as usize;
+    }
+
+    pub fn turn_right(self) -> Direction {
+        return Direction::ALL[(self.index() + 1) % 4];
+    }
+
+    pub fn turn_left(self) -> Direction {
+        return Direction::ALL[(self.index() + 3) % 4];
+    }
+
+    pub fn opposite(self) -> Direction {
+        return Direction::ALL[(self.index() + 2) % 4];
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        return self == Direction::East || self == Direction::West;
+    }
+
+    // (dx, dy) for one step
+    pub fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+            Direction::North => (0, -1),
+        }
+    }
+
+    // Compass points (NESW) or Up, Down, Left, Right
+    pub fn from_letter(c: char) -> Option<Direction> {
+        match c {
+            'E' | 'R' => Some(Direction::East),
+            'S' | 'D' => Some(Direction::South),
+            'W' | 'L' => Some(Direction::West),
+            'N' | 'U' => Some(Direction::North),
+            _ => None,
+        }
+    }
+
+    // The slopes and arrows drawn on maps
+    pub fn from_arrow(c: char) -> Option<Direction> {
+        match c {
+            '>' => Some(Direction::East),
+            'v' => Some(Direction::South),
+            '<' => Some(Direction::West),
+            '^' => Some(Direction::North),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+            Direction::North => '^',
+        }
+    }
+
+    // A hex digit from 0 (East) to 3 (North), as in the day 18 colours
+    pub fn from_hex(c: char) -> Option<Direction> {
+        let v = c.to_digit(16)? as usize;
+        return Direction::ALL.get(v).copied();
+    }
+}
+
+// A single letter or arrow
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        return match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::from_letter(c).or_else(|| Direction::from_arrow(c)),
+            _ => None,
+        }.ok_or_else(|| format!("invalid direction '{}'", s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        assert_eq!(Direction::East.turn_right(), Direction::South);
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::East.turn_left(), Direction::North);
+        assert_eq!(Direction::West.turn_left(), Direction::South);
+
+        for d in Direction::iter() {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+            assert_eq!(d.opposite().opposite(), d);
+            assert_ne!(d.opposite(), d);
+        }
+    }
+
+    #[test]
+    fn deltas() {
+        assert_eq!(Direction::iter().map(|d| d.delta()).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1), (-1, 0), (0, -1)]);
+
+        for d in Direction::iter() {
+            let (dx, dy) = d.delta();
+            assert_eq!(d.opposite().delta(), (-dx, -dy));
+            assert_eq!(d.is_horizontal(), dy == 0);
+        }
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!("U".parse::<Direction>(), Ok(Direction::North));
+        assert_eq!("R".parse::<Direction>(), Ok(Direction::East));
+        assert_eq!("W".parse::<Direction>(), Ok(Direction::West));
+        assert_eq!("v".parse::<Direction>(), Ok(Direction::South));
+        assert!("X".parse::<Direction>().is_err());
+        assert!("UD".parse::<Direction>().is_err());
+        assert!("".parse::<Direction>().is_err());
+
+        assert_eq!(Direction::from_hex('0'), Some(Direction::East));
+        assert_eq!(Direction::from_hex('3'), Some(Direction::North));
+        assert_eq!(Direction::from_hex('4'), None);
+        assert_eq!(Direction::from_hex('x'), None);
+
+        for d in Direction::iter() {
+            assert_eq!(Direction::from_arrow(d.arrow()), Some(d));
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Writing graphs in Graphviz's DOT format, for looking at puzzle inputs
+//! which are really graphs:
+//!
+//! ```text
+//! cargo run --release --bin day25 -- 25/input.txt --dot 25.dot
+//! dot -Tsvg 25.dot > 25.svg
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+// Attributes used to make highlighted nodes and edges stand out
+const HIGHLIGHT: &str = "color=red, penwidth=3";
+
+#[derive(Debug, Clone, Default)]
+pub struct Node {
+    id: String,
+    label: Option<String>,
+    shape: Option<String>,
+    highlight: bool,
+}
+
+impl Node {
+    pub fn label(&mut self, label: &str) -> &mut Node {
+        self.label = Some(label.to_string());
+        return self;
+    }
+
+    pub fn shape(&mut self, shape: &str) -> &mut Node {
+        self.shape = Some(shape.to_string());
+        return self;
+    }
+
+    pub fn highlight(&mut self) -> &mut Node {
+        self.highlight = true;
+        return self;
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Edge {
+    from: String,
+    to: String,
+    weight: Option<String>,
+    highlight: bool,
+}
+
+impl Edge {
+    // Shown as the edge's label
+    pub fn weight<W: fmt::Display>(&mut self, weight: W) -> &mut Edge {
+        self.weight = Some(weight.to_string());
+        return self;
+    }
+
+    pub fn highlight(&mut self) -> &mut Edge {
+        self.highlight = true;
+        return self;
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Cluster {
+    label: String,
+    members: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Dot {
+    directed: bool,
+    nodes: Vec<Node>,
+    // Where each node is in `nodes`
+    index: HashMap<String, usize>,
+    edges: Vec<Edge>,
+    clusters: Vec<Cluster>,
+}
+
+impl Dot {
+    pub fn digraph() -> Dot {
+        return Dot{
+            directed: true,
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            edges: Vec::new(),
+            clusters: Vec::new(),
+        };
+    }
+
+    // Undirected
+    pub fn graph() -> Dot {
+        return Dot{ directed: false, ..Dot::digraph() };
+    }
+
+    // The node called `id`, which is added if it isn't there yet. Nodes only
+    // need adding to give them attributes, as edges add their own.
+    pub fn node(&mut self, id: &str) -> &mut Node {
+        let i = match self.index.get(id) {
+            Some(&i) => i,
+            None => {
+                self.index.insert(id.to_string(), self.nodes.len());
+                self.nodes.push(Node{ id: id.to_string(), ..Node::default() });
+                self.nodes.len() - 1
+            },
+        };
+
+        return &mut self.nodes[i];
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str) -> &mut Edge {
+        self.edges.push(Edge{ from: from.to_string(), to: to.to_string(), ..Edge::default() });
+        return self.edges.last_mut().unwrap();
+    }
+
+    // Draws a box labelled `label` around `members`
+    pub fn cluster<I: IntoIterator<Item = S>, S: AsRef<str>>(&mut self, label: &str, members: I) {
+        let members = members.into_iter().map(|m| m.as_ref().to_string()).collect();
+        self.clusters.push(Cluster{ label: label.to_string(), members });
+    }
+}
+
+// Quotes and escapes `s` as a DOT string, where newlines become "\n" line
+// breaks in labels
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+
+    return out;
+}
+
+fn attributes(attrs: &[String]) -> String {
+    if attrs.is_empty() {
+        return String::new();
+    }
+
+    return format!(" [{}]", attrs.join(", "));
+}
+
+impl fmt::Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (kind, arrow) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+        writeln!(f, "{} G {{", kind)?;
+
+        for (i, cluster) in self.clusters.iter().enumerate() {
+            writeln!(f, "    subgraph cluster_{} {{", i)?;
+            writeln!(f, "        label={};", quote(&cluster.label))?;
+            for m in &cluster.members {
+                writeln!(f, "        {};", quote(m))?;
+            }
+            writeln!(f, "    }}")?;
+        }
+
+        for node in &self.nodes {
+            let mut attrs = Vec::new();
+            if let Some(label) = &node.label {
+                attrs.push(format!("label={}", quote(label)));
+            }
+            if let Some(shape) = &node.shape {
+                attrs.push(format!("shape={}", shape));
+            }
+            if node.highlight {
+                attrs.push(HIGHLIGHT.to_string());
+            }
+            writeln!(f, "    {}{};", quote(&node.id), attributes(&attrs))?;
+        }
+
+        for edge in &self.edges {
+            let mut attrs = Vec::new();
+            if let Some(weight) = &edge.weight {
+                attrs.push(format!("label={}", quote(weight)));
+            }
+            if edge.highlight {
+                attrs.push(HIGHLIGHT.to_string());
+            }
+            writeln!(f, "    {} {} {}{};", quote(&edge.from), arrow, quote(&edge.to), attributes(&attrs))?;
+        }
+
+        return writeln!(f, "}}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directed() {
+        let mut dot = Dot::digraph();
+        dot.node("a").label("A\nflip-flop").shape("box");
+        dot.node("b").highlight();
+        dot.edge("a", "b").weight(3);
+        dot.edge("b", "a").highlight();
+        dot.edge("b", "c");
+
+        assert_eq!(dot.to_string(), concat!(
+            "digraph G {\n",
+            "    \"a\" [label=\"A\\nflip-flop\", shape=box];\n",
+            "    \"b\" [color=red, penwidth=3];\n",
+            "    \"a\" -> \"b\" [label=\"3\"];\n",
+            "    \"b\" -> \"a\" [color=red, penwidth=3];\n",
+            "    \"b\" -> \"c\";\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn undirected_with_clusters() {
+        let mut dot = Dot::graph();
+        dot.cluster("left", ["x", "y"]);
+        dot.edge("x", "y");
+        dot.node("x").label("first");
+        // Adding it again changes the same node
+        dot.node("x").label("\"quoted\"");
+
+        assert_eq!(dot.to_string(), concat!(
+            "graph G {\n",
+            "    subgraph cluster_0 {\n",
+            "        label=\"left\";\n",
+            "        \"x\";\n",
+            "        \"y\";\n",
+            "    }\n",
+            "    \"x\" [label=\"\\\"quoted\\\"\"];\n",
+            "    \"x\" -- \"y\";\n",
+            "}\n",
+        ));
+    }
+}
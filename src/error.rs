@@ -0,0 +1,195 @@
+//! Error type for puzzle input parsing.
+//!
+//! A `ParseErr` says what was wrong and with which bit of text, and where
+//! that text is in the input so that it can be found and fixed. The parsers
+//! mostly work on slices of the whole input, so `ParseErr::at` can work out
+//! the line and column from the slice itself.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseErr {
+    // Filled in by whoever read the input, the parsers don't know it
+    pub file: Option<String>,
+    // 1-based, 0 if not known
+    pub line: usize,
+    pub col: usize,
+    // The offending text, empty if the input ended too soon
+    pub token: String,
+    pub msg: String,
+}
+
+// Byte offset of `token` in `input`, if it's a slice of it
+fn offset_in(input: &str, token: &str) -> Option<usize> {
+    let start = input.as_ptr() as usize;
+    let pos = token.as_ptr() as usize;
+
+    if pos < start || pos + token.len() > start + input.len() {
+        return None;
+    }
+
+    return Some(pos - start);
+}
+
+impl ParseErr {
+    // An error without a location (yet), for FromStr impls and the like which
+    // only see part of the input. See `within`.
+    pub fn new(token: &str, msg: &str) -> ParseErr {
+        return ParseErr{
+            file: None,
+            line: 0,
+            col: 0,
+            token: token.to_string(),
+            msg: msg.to_string(),
+        };
+    }
+
+    // An error for `token`, which must be a slice of `input` to get a location
+    pub fn at(input: &str, token: &str, msg: &str) -> ParseErr {
+        let mut err = ParseErr::new(token, msg);
+
+        if let Some(offset) = offset_in(input, token) {
+            let before = &input[..offset];
+            let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+            err.line = before.matches('\n').count() + 1;
+            err.col = before[line_start..].chars().count() + 1;
+        }
+
+        return err;
+    }
+
+    // For when the input runs out before `s` (a slice of `input`) is done
+    pub fn end_of(input: &str, s: &str, msg: &str) -> ParseErr {
+        let end = match offset_in(input, s) {
+            Some(offset) => offset + s.len(),
+            None => input.len(),
+        };
+
+        return ParseErr::at(input, &input[end..end], msg);
+    }
+
+    // Places an error from parsing `s` within the whole `input`, by finding
+    // its token in `s`. Errors which already have a location are left alone.
+    pub fn within(self, input: &str, s: &str) -> ParseErr {
+        if self.line != 0 {
+            return self;
+        }
+
+        let token = match s.find(&self.token) {
+            Some(idx) => &s[idx..idx + self.token.len()],
+            None => s,
+        };
+
+        let mut err = ParseErr::at(input, token, &self.msg);
+        err.token = self.token;
+        return err;
+    }
+
+    pub fn in_file(mut self, file: &str) -> ParseErr {
+        self.file = Some(file.to_string());
+        return self;
+    }
+}
+
+impl Error for ParseErr {}
+
+impl fmt::Display for ParseErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        if self.line != 0 {
+            write!(f, "{}:{}:", self.line, self.col)?;
+        }
+        if self.file.is_some() || self.line != 0 {
+            write!(f, " ")?;
+        }
+
+        if self.token.is_empty() {
+            write!(f, "{}, found end of input", self.msg)
+        } else {
+            write!(f, "{}, found '{}'", self.msg, self.token)
+        }
+    }
+}
+
+// Parses `token` (a slice of `input`) as a number
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseErr> {
+    return token.parse::<T>()
+        .map_err(|_| ParseErr::at(input, token, "expected a number"));
+}
+
+// Like str::split_once, but it's an error for `delim` to be missing from `s`
+pub fn split<'a>(input: &str, s: &'a str, delim: &str) -> Result<(&'a str, &'a str), ParseErr> {
+    return s.split_once(delim)
+        .ok_or_else(|| ParseErr::at(input, s, &format!("expected '{}'", delim)));
+}
+
+// The next line of the input, or an error if there aren't any more
+pub fn next_line<'a, I>(input: &str, lines: &mut I, what: &str) -> Result<&'a str, ParseErr>
+where
+    I: Iterator<Item = &'a str>,
+{
+    return lines.next()
+        .ok_or_else(|| ParseErr::end_of(input, input, &format!("expected {}", what)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "12 34\n56 x8\n";
+
+    #[test]
+    fn display() {
+        let e = ParseErr::new("x8", "expected a number");
+        assert_eq!(e.to_string(), "expected a number, found 'x8'");
+
+        let e = e.within(INPUT, &INPUT[6..11]);
+        assert_eq!(e.to_string(), "2:4: expected a number, found 'x8'");
+
+        let e = e.in_file("foo.txt");
+        assert_eq!(e.to_string(), "foo.txt:2:4: expected a number, found 'x8'");
+    }
+
+    #[test]
+    fn location() {
+        let e = ParseErr::at(INPUT, &INPUT[3..5], "oops");
+        assert_eq!((e.line, e.col, e.token.as_str()), (1, 4, "34"));
+
+        // Not a slice of the input, so there's no location
+        let e = ParseErr::at(INPUT, "34", "oops");
+        assert_eq!((e.line, e.col), (0, 0));
+    }
+
+    #[test]
+    fn end_of_input() {
+        let e = ParseErr::end_of(INPUT, INPUT, "expected a line");
+        assert_eq!(e.to_string(), "3:1: expected a line, found end of input");
+
+        let mut lines = "a\n".lines();
+        assert!(next_line("a\n", &mut lines, "a line").is_ok());
+        assert!(next_line("a\n", &mut lines, "a line").is_err());
+    }
+
+    #[test]
+    fn helpers() {
+        let line = &INPUT[6..11];
+        assert_eq!(number::<u32>(INPUT, &line[..2]), Ok(56));
+
+        let e = number::<u32>(INPUT, &line[3..]).unwrap_err();
+        assert_eq!((e.line, e.col), (2, 4));
+
+        assert_eq!(split(INPUT, line, " "), Ok(("56", "x8")));
+        assert_eq!(split(INPUT, line, ":").unwrap_err().col, 1);
+    }
+
+    #[test]
+    fn boxes_as_error() {
+        let e: Box<dyn Error> = Box::new(ParseErr::new("", "expected a line"));
+        assert_eq!(e.to_string(), "expected a line, found end of input");
+    }
+}
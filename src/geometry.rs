@@ -0,0 +1,473 @@
+//! Points and vectors in 2D and 3D space, and boxes made of them.
+//!
+//! Everything works on any of the integer types, and the arithmetic is exact:
+//! the operators overflow the same way the underlying type does, and there
+//! are `checked_*` versions, or `widen()` to do the sums in i128 instead.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::error::ParseErr;
+
+pub trait Coord: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn widen(self) -> i128;
+
+    // |self - other|, which works for unsigned types too
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            return self - other;
+        }
+        return other - self;
+    }
+}
+
+macro_rules! coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn widen(self) -> i128 {
+                    self as i128
+                }
+            }
+        )*
+    };
+}
+
+coord!(i32, i64, i128, u32, u64, usize);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec2<T> {
+    pub fn new(x: T, y: T) -> Vec2<T> {
+        return Vec2{ x, y };
+    }
+}
+
+impl<T> Vec3<T> {
+    pub fn new(x: T, y: T, z: T) -> Vec3<T> {
+        return Vec3{ x, y, z };
+    }
+}
+
+impl<T: Coord> Vec2<T> {
+    pub fn dot(self, other: Vec2<T>) -> T {
+        return self.x * other.x + self.y * other.y;
+    }
+
+    // The z part of the 3D cross product, which is positive if `other` is
+    // anticlockwise from self (with y pointing up)
+    pub fn cross(self, other: Vec2<T>) -> T {
+        return self.x * other.y - self.y * other.x;
+    }
+
+    pub fn manhattan(self, other: Vec2<T>) -> T {
+        return self.x.abs_diff(other.x) + self.y.abs_diff(other.y);
+    }
+
+    pub fn widen(self) -> Vec2<i128> {
+        return Vec2::new(self.x.widen(), self.y.widen());
+    }
+
+    pub fn checked_add(self, other: Vec2<T>) -> Option<Vec2<T>> {
+        return Some(Vec2::new(self.x.checked_add(other.x)?, self.y.checked_add(other.y)?));
+    }
+
+    pub fn checked_sub(self, other: Vec2<T>) -> Option<Vec2<T>> {
+        return Some(Vec2::new(self.x.checked_sub(other.x)?, self.y.checked_sub(other.y)?));
+    }
+
+    pub fn checked_mul(self, n: T) -> Option<Vec2<T>> {
+        return Some(Vec2::new(self.x.checked_mul(n)?, self.y.checked_mul(n)?));
+    }
+}
+
+impl<T: Coord> Vec3<T> {
+    pub fn dot(self, other: Vec3<T>) -> T {
+        return self.x * other.x + self.y * other.y + self.z * other.z;
+    }
+
+    pub fn cross(self, other: Vec3<T>) -> Vec3<T> {
+        return Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        );
+    }
+
+    pub fn manhattan(self, other: Vec3<T>) -> T {
+        return self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z);
+    }
+
+    pub fn widen(self) -> Vec3<i128> {
+        return Vec3::new(self.x.widen(), self.y.widen(), self.z.widen());
+    }
+
+    pub fn checked_add(self, other: Vec3<T>) -> Option<Vec3<T>> {
+        return Some(Vec3::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+            self.z.checked_add(other.z)?,
+        ));
+    }
+
+    pub fn checked_sub(self, other: Vec3<T>) -> Option<Vec3<T>> {
+        return Some(Vec3::new(
+            self.x.checked_sub(other.x)?,
+            self.y.checked_sub(other.y)?,
+            self.z.checked_sub(other.z)?,
+        ));
+    }
+
+    pub fn checked_mul(self, n: T) -> Option<Vec3<T>> {
+        return Some(Vec3::new(self.x.checked_mul(n)?, self.y.checked_mul(n)?, self.z.checked_mul(n)?));
+    }
+
+    // Looking down from above
+    pub fn xy(self) -> Vec2<T> {
+        return Vec2::new(self.x, self.y);
+    }
+}
+
+// The component-wise operators, and multiplying by a scalar
+macro_rules! vector_ops {
+    ($v:ident, $($f:ident),*) => {
+        impl<T: Add<Output = T>> Add for $v<T> {
+            type Output = $v<T>;
+
+            fn add(self, other: $v<T>) -> $v<T> {
+                $v{ $($f: self.$f + other.$f),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $v<T> {
+            type Output = $v<T>;
+
+            fn sub(self, other: $v<T>) -> $v<T> {
+                $v{ $($f: self.$f - other.$f),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $v<T> {
+            type Output = $v<T>;
+
+            fn neg(self) -> $v<T> {
+                $v{ $($f: -self.$f),* }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $v<T> {
+            type Output = $v<T>;
+
+            fn mul(self, n: T) -> $v<T> {
+                $v{ $($f: self.$f * n),* }
+            }
+        }
+
+        impl<T: Copy + Add<Output = T>> AddAssign for $v<T> {
+            fn add_assign(&mut self, other: $v<T>) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> SubAssign for $v<T> {
+            fn sub_assign(&mut self, other: $v<T>) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T: Coord> Vector for $v<T> {
+            type T = T;
+
+            fn zip_map<F: Fn(T, T) -> T>(self, other: $v<T>, f: F) -> $v<T> {
+                $v{ $($f: f(self.$f, other.$f)),* }
+            }
+
+            fn fold<A, F: Fn(A, T, T) -> A>(self, other: $v<T>, init: A, f: F) -> A {
+                let acc = init;
+                $(let acc = f(acc, self.$f, other.$f);)*
+                acc
+            }
+        }
+    };
+}
+
+vector_ops!(Vec2, x, y);
+vector_ops!(Vec3, x, y, z);
+
+impl<T: fmt::Display> fmt::Display for Vec2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{},{}", self.x, self.y);
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vec3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{},{},{}", self.x, self.y, self.z);
+    }
+}
+
+// Splits "a,b,c" into exactly `n` numbers. Whitespace around each one is
+// ignored, so both "1,2,3" and "1, 2, 3" work.
+fn parse_components<T: FromStr>(s: &str, n: usize, expected: &str) -> Result<Vec<T>, ParseErr> {
+    let parts: Vec<&str> = s.split(',').map(|p| p.trim()).collect();
+    if parts.len() != n {
+        return Err(ParseErr::new(s, expected));
+    }
+
+    return parts.iter()
+        .map(|p| p.parse::<T>().or(Err(ParseErr::new(p, "expected a number"))))
+        .collect();
+}
+
+// Parses "x,y"
+impl<T: FromStr + Copy> FromStr for Vec2<T> {
+    type Err = ParseErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let v = parse_components(s, 2, "expected 'x,y'")?;
+        return Ok(Vec2::new(v[0], v[1]));
+    }
+}
+
+// Parses "x,y,z"
+impl<T: FromStr + Copy> FromStr for Vec3<T> {
+    type Err = ParseErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let v = parse_components(s, 3, "expected 'x,y,z'")?;
+        return Ok(Vec3::new(v[0], v[1], v[2]));
+    }
+}
+
+// Ordered by y first, which is reading order on a map
+impl<T: Ord> Ord for Vec2<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.y.cmp(&other.y)
+            .then_with(|| self.x.cmp(&other.x))
+    }
+}
+
+impl<T: Ord> PartialOrd for Vec2<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Ordered by z first, so that sorting puts the lowest points first
+impl<T: Ord> Ord for Vec3<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.z.cmp(&other.z)
+            .then_with(|| self.y.cmp(&other.y))
+            .then_with(|| self.x.cmp(&other.x))
+    }
+}
+
+impl<T: Ord> PartialOrd for Vec3<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// What Aabb needs to work in any number of dimensions
+pub trait Vector: Copy + Eq {
+    type T: Coord;
+
+    // Combines each component of self with the same one of other
+    fn zip_map<F: Fn(Self::T, Self::T) -> Self::T>(self, other: Self, f: F) -> Self;
+
+    // Folds over pairs of components
+    fn fold<A, F: Fn(A, Self::T, Self::T) -> A>(self, other: Self, init: A, f: F) -> A;
+}
+
+// An axis-aligned box, including both corners
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Aabb<V> {
+    pub min: V,
+    pub max: V,
+}
+
+impl<V: Vector> Aabb<V> {
+    // The box with `a` and `b` at opposite corners
+    pub fn new(a: V, b: V) -> Aabb<V> {
+        return Aabb{ min: a.zip_map(b, Ord::min), max: a.zip_map(b, Ord::max) };
+    }
+
+    // The smallest box around all of `points`, or None if there aren't any
+    pub fn around<I: IntoIterator<Item = V>>(points: I) -> Option<Aabb<V>> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+
+        return Some(points.fold(Aabb::new(first, first), |b, p| b.expand(p)));
+    }
+
+    // Grown just enough to include `p`
+    pub fn expand(&self, p: V) -> Aabb<V> {
+        return Aabb{ min: self.min.zip_map(p, Ord::min), max: self.max.zip_map(p, Ord::max) };
+    }
+
+    pub fn contains(&self, p: V) -> bool {
+        return self.min.fold(p, true, |ok, lo, v| ok && lo <= v) &&
+            p.fold(self.max, true, |ok, v, hi| ok && v <= hi);
+    }
+
+    pub fn intersects(&self, other: &Aabb<V>) -> bool {
+        return self.intersection(other).is_some();
+    }
+
+    pub fn intersection(&self, other: &Aabb<V>) -> Option<Aabb<V>> {
+        let min = self.min.zip_map(other.min, Ord::max);
+        let max = self.max.zip_map(other.max, Ord::min);
+
+        if !min.fold(max, true, |ok, lo, hi| ok && lo <= hi) {
+            return None;
+        }
+
+        return Some(Aabb{ min, max });
+    }
+
+    // The number of integer points inside, which is the area in 2D
+    pub fn volume(&self) -> i128 {
+        return self.min.fold(self.max, 1, |n, lo, hi| n * (hi.widen() - lo.widen() + 1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("1,0,1".parse::<Vec3<u32>>(), Ok(Vec3::new(1, 0, 1)));
+        assert_eq!("19, 13, 30".parse::<Vec3<i64>>(), Ok(Vec3::new(19, 13, 30)));
+        assert_eq!("-2,  1, -2".parse::<Vec3<i64>>(), Ok(Vec3::new(-2, 1, -2)));
+        assert_eq!("3, -4".parse::<Vec2<i32>>(), Ok(Vec2::new(3, -4)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!("1,2".parse::<Vec3<u32>>().unwrap_err().token, "1,2");
+        assert_eq!("1,2,3,4".parse::<Vec3<u32>>().unwrap_err().token, "1,2,3,4");
+        assert_eq!("1,x,3".parse::<Vec3<u32>>().unwrap_err().token, "x");
+        assert_eq!("1, -2,3".parse::<Vec3<u32>>().unwrap_err().token, "-2");
+        assert_eq!("1,2,3".parse::<Vec2<u32>>().unwrap_err().token, "1,2,3");
+    }
+
+    #[test]
+    fn ordered_by_z_then_y_then_x() {
+        let mut points = vec![
+            Vec3::new(0, 0, 2),
+            Vec3::new(1, 0, 1),
+            Vec3::new(0, 1, 1),
+            Vec3::new(0, 0, 1),
+        ];
+        points.sort();
+
+        assert_eq!(points, vec![
+            Vec3::new(0, 0, 1),
+            Vec3::new(1, 0, 1),
+            Vec3::new(0, 1, 1),
+            Vec3::new(0, 0, 2),
+        ]);
+
+        let mut points = vec![Vec2::new(1, 1), Vec2::new(2, 0), Vec2::new(0, 1)];
+        points.sort();
+        assert_eq!(points, vec![Vec2::new(2, 0), Vec2::new(0, 1), Vec2::new(1, 1)]);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Vec3::new(1, 2, 3);
+        let b = Vec3::new(4, -5, 6);
+        assert_eq!(a + b, Vec3::new(5, -3, 9));
+        assert_eq!(a - b, Vec3::new(-3, 7, -3));
+        assert_eq!(-a, Vec3::new(-1, -2, -3));
+        assert_eq!(a * 3, Vec3::new(3, 6, 9));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+
+        assert_eq!(a.dot(b), 4 - 10 + 18);
+        assert_eq!(a.cross(b), Vec3::new(27, 6, -13));
+        assert_eq!(a.cross(b).dot(a), 0);
+        assert_eq!(a.manhattan(b), 3 + 7 + 3);
+        assert_eq!(a.xy(), Vec2::new(1, 2));
+
+        let p = Vec2::new(3u32, 9);
+        let q = Vec2::new(7u32, 2);
+        assert_eq!(p.manhattan(q), 11);
+        assert_eq!(q.manhattan(p), 11);
+        assert_eq!(Vec2::new(1, 0).cross(Vec2::new(0, 1)), 1);
+        assert_eq!(Vec2::new(2, 3).dot(Vec2::new(4, 5)), 23);
+    }
+
+    #[test]
+    fn overflow() {
+        let big = Vec3::new(i64::MAX / 2, 0, i64::MIN / 2);
+        assert_eq!(big.checked_add(big), Some(Vec3::new(i64::MAX - 1, 0, i64::MIN)));
+        assert_eq!(big.checked_mul(3), None);
+        assert_eq!(big.checked_sub(-big), Some(big * 2));
+        assert_eq!(Vec2::new(0u32, 1).checked_sub(Vec2::new(1, 0)), None);
+
+        // Cross products of i64 positions need more room
+        let p = Vec3::new(200_000_000_000_000i64, 300_000_000_000_000, 1);
+        let v = Vec3::new(-3i64, 5, 7);
+        assert_eq!(p.checked_mul(p.x), None);
+        let w = p.widen().cross(v.widen());
+        assert_eq!(w.z, 200_000_000_000_000 * 5 + 300_000_000_000_000 * 3);
+    }
+
+    #[test]
+    fn boxes() {
+        let a = Aabb::new(Vec3::new(3, 0, 5), Vec3::new(1, 2, 5));
+        assert_eq!((a.min, a.max), (Vec3::new(1, 0, 5), Vec3::new(3, 2, 5)));
+        assert_eq!(a.volume(), 9);
+        assert!(a.contains(Vec3::new(2, 2, 5)));
+        assert!(!a.contains(Vec3::new(2, 2, 4)));
+
+        let b = Aabb::new(Vec3::new(3, 2, 0), Vec3::new(9, 9, 9));
+        assert_eq!(a.intersection(&b), Some(Aabb::new(Vec3::new(3, 2, 5), Vec3::new(3, 2, 5))));
+        let c = Aabb::new(Vec3::new(4, 0, 0), Vec3::new(9, 9, 9));
+        assert!(!a.intersects(&c));
+
+        let around = Aabb::around([Vec2::new(1u32, 5), Vec2::new(4, 2), Vec2::new(2, 3)]).unwrap();
+        assert_eq!(around, Aabb::new(Vec2::new(1, 2), Vec2::new(4, 5)));
+        assert_eq!(around.volume(), 16);
+        assert_eq!(Aabb::<Vec2<u32>>::around([]), None);
+    }
+}
@@ -0,0 +1,406 @@
+//! Shortest paths through graphs which are only described by a neighbour
+//! function, so the nodes can be anything from a position on a map to the
+//! whole state of a puzzle.
+//!
+//! Everything takes several starting nodes, because "start from any of
+//! these" comes up often enough, and can give back the path as well as its
+//! length.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+pub trait Cost: Copy + Ord + Add<Output = Self> {
+    const ZERO: Self;
+}
+
+macro_rules! cost {
+    ($($t:ty),*) => {
+        $(
+            impl Cost for $t {
+                const ZERO: Self = 0;
+            }
+        )*
+    };
+}
+
+cost!(u8, u16, u32, u64, u128, usize, i32, i64, i128);
+
+// The shortest distance to every node which was reached, and where each one
+// was reached from
+#[derive(Debug, Clone)]
+pub struct Distances<N, C> {
+    pub dist: HashMap<N, C>,
+    prev: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Distances<N, C> {
+    pub fn get(&self, node: &N) -> Option<C> {
+        return self.dist.get(node).copied();
+    }
+
+    // From one of the starts to `node`, including both ends
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.dist.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        while let Some(p) = self.prev.get(path.last().unwrap()) {
+            path.push(p.clone());
+        }
+        path.reverse();
+
+        return Some(path);
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    // From the start to the goal, including both
+    pub nodes: Vec<N>,
+}
+
+fn unvisited<N: Clone + Eq + Hash, C: Copy>(starts: impl IntoIterator<Item = N>, zero: C) -> (Distances<N, C>, Vec<N>) {
+    let mut dist = HashMap::new();
+    let mut order = Vec::new();
+    for s in starts {
+        if !dist.contains_key(&s) {
+            dist.insert(s.clone(), zero);
+            order.push(s);
+        }
+    }
+
+    return (Distances{ dist, prev: HashMap::new() }, order);
+}
+
+// Breadth first until `is_goal` is true of some node, returning everything
+// seen so far, and the goal if it was found
+fn bfs_until<N, F, I, G>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: F,
+    mut is_goal: G,
+) -> (Distances<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let (mut seen, starts) = unvisited(starts, 0);
+    let mut to_visit = VecDeque::from(starts);
+
+    while let Some(node) = to_visit.pop_front() {
+        if is_goal(&node) {
+            return (seen, Some(node));
+        }
+
+        let d = seen.dist[&node];
+        for next in neighbours(&node) {
+            if !seen.dist.contains_key(&next) {
+                seen.dist.insert(next.clone(), d + 1);
+                seen.prev.insert(next.clone(), node.clone());
+                to_visit.push_back(next);
+            }
+        }
+    }
+
+    return (seen, None);
+}
+
+// The number of steps to everything reachable from the starts
+pub fn bfs<N, F, I>(starts: impl IntoIterator<Item = N>, neighbours: F) -> Distances<N, usize>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    return bfs_until(starts, neighbours, |_| false).0;
+}
+
+// The fewest steps to a goal, stopping as soon as one is found
+pub fn bfs_path<N, F, I, G>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: F,
+    is_goal: G,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let (seen, goal) = bfs_until(starts, neighbours, is_goal);
+    let goal = goal?;
+
+    return Some(Path{ cost: seen.dist[&goal], nodes: seen.path_to(&goal)? });
+}
+
+// Waiting to be explored, ordered so that BinaryHeap (a max-heap) gives the
+// lowest estimate first
+struct Entry<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return other.estimate.cmp(&self.estimate)
+            .then_with(|| other.cost.cmp(&self.cost));
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.cmp(other) == Ordering::Equal;
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+// A* until a goal is reached, which is Dijkstra when the heuristic is zero.
+// Nodes can be visited again if a cheaper way to them turns up, so the
+// heuristic only has to be admissible (never overestimate), not consistent.
+fn search<N, C, F, I, H, G>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: F,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> (Distances<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let (mut best, starts) = unvisited(starts, C::ZERO);
+    let mut to_explore = BinaryHeap::new();
+    for node in starts {
+        to_explore.push(Entry{ estimate: heuristic(&node), cost: C::ZERO, node });
+    }
+
+    while let Some(Entry{ cost, node, .. }) = to_explore.pop() {
+        // Already found a cheaper way here
+        if cost > best.dist[&node] {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return (best, Some(node));
+        }
+
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if best.dist.get(&next).is_some_and(|&c| c <= next_cost) {
+                continue;
+            }
+
+            best.dist.insert(next.clone(), next_cost);
+            best.prev.insert(next.clone(), node.clone());
+            to_explore.push(Entry{ estimate: next_cost + heuristic(&next), cost: next_cost, node: next });
+        }
+    }
+
+    return (best, None);
+}
+
+// The cheapest cost to everything reachable from the starts
+pub fn dijkstra_all<N, C, F, I>(starts: impl IntoIterator<Item = N>, neighbours: F) -> Distances<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    return search(starts, neighbours, |_| C::ZERO, |_| false).0;
+}
+
+// The cheapest path to a goal
+pub fn dijkstra<N, C, F, I, G>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: F,
+    is_goal: G,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    return astar(starts, neighbours, |_| C::ZERO, is_goal);
+}
+
+// The cheapest path to a goal, guided by `heuristic`, which must never be more
+// than the real cost from a node to the nearest goal
+pub fn astar<N, C, F, I, H, G>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: F,
+    heuristic: H,
+    is_goal: G,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let (best, goal) = search(starts, neighbours, heuristic, is_goal);
+    let goal = goal?;
+
+    return Some(Path{ cost: best.dist[&goal], nodes: best.path_to(&goal)? });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: [&str; 5] = [
+        "S.#....",
+        ".##.##.",
+        "...#...",
+        ".#...#E",
+        "...#...",
+    ];
+
+    fn open(pos: (i32, i32)) -> bool {
+        let (x, y) = pos;
+        if x < 0 || y < 0 || y as usize >= MAZE.len() || x as usize >= MAZE[0].len() {
+            return false;
+        }
+
+        return MAZE[y as usize].as_bytes()[x as usize] != b'#';
+    }
+
+    fn steps(pos: &(i32, i32)) -> Vec<(i32, i32)> {
+        let (x, y) = *pos;
+        return [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)].into_iter()
+            .filter(|&p| open(p))
+            .collect();
+    }
+
+    fn is_path(nodes: &Vec<(i32, i32)>) -> bool {
+        return nodes.windows(2).all(|w| steps(&w[0]).contains(&w[1]));
+    }
+
+    const END: (i32, i32) = (6, 3);
+
+    #[test]
+    fn breadth_first() {
+        let seen = bfs([(0, 0)], steps);
+        assert_eq!(seen.get(&(0, 0)), Some(0));
+        assert_eq!(seen.get(&END), Some(11));
+        assert_eq!(seen.get(&(2, 0)), None);
+        assert_eq!(seen.path_to(&(2, 0)), None);
+
+        let path = seen.path_to(&END).unwrap();
+        assert_eq!(path.len(), 12);
+        assert_eq!((path[0], path[11]), ((0, 0), END));
+        assert!(is_path(&path));
+
+        let found = bfs_path([(0, 0)], steps, |&p| p == END).unwrap();
+        assert_eq!(found.cost, 11);
+        assert_eq!(found.nodes, path);
+    }
+
+    #[test]
+    fn weighted() {
+        let unit = |p: &(i32, i32)| steps(p).into_iter().map(|n| (n, 1u32)).collect::<Vec<_>>();
+        let d = dijkstra([(0, 0)], unit, |&p| p == END).unwrap();
+        let a = astar([(0, 0)], unit, |&(x, y)| ((END.0 - x).abs() + (END.1 - y).abs()) as u32, |&p| p == END).unwrap();
+        assert_eq!((d.cost, a.cost), (11, 11));
+        assert!(is_path(&d.nodes) && is_path(&a.nodes));
+
+        // Going down costs 10, and there are four rows to go down
+        let downhill = |p: &(i32, i32)| steps(p).into_iter()
+            .map(|n| (n, if n.1 > p.1 { 10u32 } else { 1 }))
+            .collect::<Vec<_>>();
+        let path = dijkstra([(0, 0)], downhill, |&p| p == (0, 4)).unwrap();
+        assert_eq!(path.cost, 40);
+        assert_eq!(dijkstra_all([(0, 0)], downhill).get(&(0, 4)), Some(40));
+    }
+
+    #[test]
+    fn starts_and_goals() {
+        // Already there
+        let path = bfs_path([(0, 0)], steps, |_| true).unwrap();
+        assert_eq!(path, Path{ cost: 0, nodes: vec![(0, 0)] });
+
+        // The nearest start wins
+        let path = bfs_path([(0, 0), (6, 0)], steps, |&p| p == END).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.nodes[0], (6, 0));
+
+        // Walled off
+        let unit = |p: &(i32, i32)| steps(p).into_iter().map(|n| (n, 1u32)).collect::<Vec<_>>();
+        assert_eq!(dijkstra([(0, 0)], unit, |&p| p == (2, 0)), None);
+        assert_eq!(bfs_path([(0, 0)], steps, |&p| p == (2, 0)), None);
+    }
+
+    #[test]
+    fn against_floyd_warshall() {
+        // Small random graphs, including zero cost edges
+        let mut seed = 0x1234_5678_9abc_def0u64;
+        let mut rand = move |n: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            return seed % n;
+        };
+
+        for _ in 0..200 {
+            let n = 1 + rand(8) as usize;
+            let mut edges = vec![Vec::new(); n];
+            for _ in 0..rand(20) {
+                edges[rand(n as u64) as usize].push((rand(n as u64) as usize, rand(5)));
+            }
+
+            let mut expected = vec![vec![None; n]; n];
+            for (a, out) in edges.iter().enumerate() {
+                expected[a][a] = Some(0);
+                for &(b, c) in out {
+                    expected[a][b] = Some(expected[a][b].map_or(c, |e: u64| e.min(c)));
+                }
+            }
+            for k in 0..n {
+                for a in 0..n {
+                    for b in 0..n {
+                        if let (Some(x), Some(y)) = (expected[a][k], expected[k][b]) {
+                            expected[a][b] = Some(expected[a][b].map_or(x + y, |e| e.min(x + y)));
+                        }
+                    }
+                }
+            }
+
+            let neighbours = |a: &usize| edges[*a].clone();
+            for (a, row) in expected.iter().enumerate() {
+                let all = dijkstra_all([a], neighbours);
+                for (b, &want) in row.iter().enumerate() {
+                    assert_eq!(all.get(&b), want);
+
+                    let path = dijkstra([a], neighbours, |&x| x == b);
+                    assert_eq!(path.as_ref().map(|p| p.cost), want);
+                    if let Some(path) = path {
+                        let total: u64 = path.nodes.windows(2)
+                            .map(|w| edges[w[0]].iter().filter(|e| e.0 == w[1]).map(|e| e.1).min().unwrap())
+                            .sum();
+                        assert_eq!(total, path.cost);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,388 @@
+//! 2D maps, like the ones most of the puzzles are drawn on.
+//!
+//! Positions are `(x, y)` with y pointing down, so `(0, 0)` is the top-left
+//! of the map. Steps and lookups which might fall off the edge take signed
+//! coordinates and return `None` rather than panicking.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseErr;
+
+// Checks that `s` (a slice of `input`) is a non-empty rectangle made only of
+// characters in `allowed`, so that it can be indexed without surprises.
+pub fn check(input: &str, s: &str, allowed: &str) -> Result<(), ParseErr> {
+    let mut width = None;
+
+    for line in s.lines() {
+        if let Some((idx, c)) = line.char_indices().find(|(_, c)| !allowed.contains(*c)) {
+            let token = &line[idx..idx + c.len_utf8()];
+            return Err(ParseErr::at(input, token, &format!("expected one of \"{}\"", allowed)));
+        }
+
+        // In characters, as they'll be in the grid
+        let len = line.chars().count();
+        match width {
+            None => width = Some(len),
+            Some(w) if w != len => {
+                return Err(ParseErr::at(input, line, &format!("expected a row of width {}", w)));
+            },
+            _ => {},
+        }
+    }
+
+    if width.is_none_or(|w| w == 0) {
+        return Err(ParseErr::end_of(input, s, "expected a map"));
+    }
+
+    return Ok(());
+}
+
+// The 4 orthogonal neighbours, then the 4 diagonals
+const NEIGHBOURS: [(i32, i32); 8] = [
+    (1, 0), (0, 1), (-1, 0), (0, -1),
+    (1, 1), (-1, 1), (-1, -1), (1, -1),
+];
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    // Row-major
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        return Grid{ width, height, cells: vec![fill; width * height] };
+    }
+
+    // Swaps rows for columns, mirroring the map along its main diagonal
+    pub fn transpose(&self) -> Grid<T> {
+        let cells = (0..self.width)
+            .flat_map(|x| self.col(x).cloned())
+            .collect();
+
+        return Grid{ width: self.height, height: self.width, cells };
+    }
+
+    // A quarter turn clockwise, so the left column becomes the top row
+    pub fn rotate_cw(&self) -> Grid<T> {
+        let cells = (0..self.width)
+            .flat_map(|x| self.col(x).rev().cloned())
+            .collect();
+
+        return Grid{ width: self.height, height: self.width, cells };
+    }
+
+    // A quarter turn anticlockwise, so the right column becomes the top row
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        let cells = (0..self.width).rev()
+            .flat_map(|x| self.col(x).cloned())
+            .collect();
+
+        return Grid{ width: self.height, height: self.width, cells };
+    }
+}
+
+impl<T> Grid<T> {
+    // Panics if the rows aren't all the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        assert!(rows.iter().all(|r| r.len() == width), "rows must all be the same length");
+
+        return Grid{ width, height, cells: rows.into_iter().flatten().collect() };
+    }
+
+    // Parses `s` (a slice of `input`), converting each character with `f`.
+    // The map must be a non-empty rectangle of characters from `allowed`.
+    pub fn parse_with<F: Fn(char) -> T>(input: &str, s: &str, allowed: &str, f: F) -> Result<Grid<T>, ParseErr> {
+        check(input, s, allowed)?;
+
+        let rows = s.lines()
+            .map(|line| line.chars().map(&f).collect())
+            .collect();
+
+        return Ok(Grid::from_rows(rows));
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    pub fn contains(&self, pos: (i32, i32)) -> bool {
+        return pos.0 >= 0 && (pos.0 as usize) < self.width &&
+            pos.1 >= 0 && (pos.1 as usize) < self.height;
+    }
+
+    pub fn get(&self, pos: (i32, i32)) -> Option<&T> {
+        if !self.contains(pos) {
+            return None;
+        }
+
+        return Some(&self.cells[pos.1 as usize * self.width + pos.0 as usize]);
+    }
+
+    pub fn get_mut(&mut self, pos: (i32, i32)) -> Option<&mut T> {
+        if !self.contains(pos) {
+            return None;
+        }
+
+        return Some(&mut self.cells[pos.1 as usize * self.width + pos.0 as usize]);
+    }
+
+    // Looks up `pos` as if the map were tiled infinitely in every direction
+    pub fn get_wrapping(&self, pos: (i32, i32)) -> &T {
+        let x = pos.0.rem_euclid(self.width as i32) as usize;
+        let y = pos.1.rem_euclid(self.height as i32) as usize;
+
+        return &self.cells[y * self.width + x];
+    }
+
+    // The position `delta` away from `pos`, if that's still on the map
+    pub fn step(&self, pos: (usize, usize), delta: (i32, i32)) -> Option<(usize, usize)> {
+        let next = (pos.0 as i32 + delta.0, pos.1 as i32 + delta.1);
+        if !self.contains(next) {
+            return None;
+        }
+
+        return Some((next.0 as usize, next.1 as usize));
+    }
+
+    // Up, down, left and right of `pos`, where they're on the map
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        return NEIGHBOURS[..4].iter().filter_map(move |&d| self.step(pos, d));
+    }
+
+    // As neighbours4(), plus the diagonals
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        return NEIGHBOURS.iter().filter_map(move |&d| self.step(pos, d));
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        return &self.cells[y * self.width..(y + 1) * self.width];
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        return (0..self.height).map(|y| self.row(y));
+    }
+
+    // The cells of column `x`, from top to bottom
+    pub fn col(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(x < self.width);
+        return self.cells.iter().skip(x).step_by(self.width.max(1));
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        return (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)));
+    }
+
+    // Every cell along with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        return self.positions().zip(self.cells.iter());
+    }
+
+    // The first cell (reading row by row) equal to `v`
+    pub fn find(&self, v: &T) -> Option<(usize, usize)>
+        where T: PartialEq
+    {
+        return self.iter().find(|(_, c)| *c == v).map(|(pos, _)| pos);
+    }
+
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> Grid<U> {
+        return Grid{
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        };
+    }
+
+    // Draws the map a line per row, with `f` choosing the character for each
+    // cell. Handy for overlaying things (like a path) which aren't in the map.
+    pub fn render<F: Fn((usize, usize), &T) -> char>(&self, f: F) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for (pos, v) in self.iter() {
+            out.push(f(pos, v));
+            if pos.0 == self.width - 1 {
+                out.push('\n');
+            }
+        }
+
+        return out;
+    }
+}
+
+impl Grid<char> {
+    pub fn parse(input: &str, s: &str, allowed: &str) -> Result<Grid<char>, ParseErr> {
+        return Grid::parse_with(input, s, allowed, |c| c);
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        assert!(pos.0 < self.width && pos.1 < self.height, "{:?} is off the map", pos);
+        return &self.cells[pos.1 * self.width + pos.0];
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        assert!(pos.0 < self.width && pos.1 < self.height, "{:?} is off the map", pos);
+        return &mut self.cells[pos.1 * self.width + pos.0];
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for v in row {
+                write!(f, "{}", v)?;
+            }
+            writeln!(f)?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(s: &str) -> Grid<char> {
+        return Grid::parse(s, s, "#.S123456789").unwrap();
+    }
+
+    #[test]
+    fn parse_chars() {
+        let g = grid("#.\n.S\n.#\n");
+        assert_eq!((g.width(), g.height()), (2, 3));
+        assert_eq!(g[(1, 1)], 'S');
+        assert_eq!(g, Grid::from_rows(vec![vec!['#', '.'], vec!['.', 'S'], vec!['.', '#']]));
+        assert_eq!(g.to_string(), "#.\n.S\n.#\n");
+    }
+
+    #[test]
+    fn parse_digits() {
+        let g = Grid::parse_with("12\n34", "12\n34", "0123456789", |c| c.to_digit(10).unwrap() as u8).unwrap();
+        assert_eq!(g, Grid::from_rows(vec![vec![1, 2], vec![3, 4]]));
+
+        let e = Grid::parse_with("12\n3x", "12\n3x", "0123456789", |c| c as u8).unwrap_err();
+        assert_eq!((e.line, e.col, e.token.as_str()), (2, 2, "x"));
+    }
+
+    #[test]
+    fn check_shape() {
+        let input = "#.\n.S\n\n#.\n.\n\n#x\n";
+        assert!(check(input, &input[..6], "#.S").is_ok());
+
+        let e = check(input, &input[7..], "#.").unwrap_err();
+        assert_eq!((e.line, e.col, e.token.as_str()), (5, 1, "."));
+
+        let e = check(input, &input[13..], "#.").unwrap_err();
+        assert_eq!((e.line, e.col, e.token.as_str()), (7, 2, "x"));
+
+        assert!(check(input, &input[12..13], "#.").is_err());
+
+        // Which is the same width, even though "·" is two bytes
+        assert!(check("#·\n·#\n.#\n", "#·\n·#\n.#\n", "#.·").is_ok());
+    }
+
+    #[test]
+    fn lookups() {
+        let mut g = grid("123\n456\n");
+        assert_eq!(g.get((2, 1)), Some(&'6'));
+        assert_eq!(g.get((3, 1)), None);
+        assert_eq!(g.get((0, -1)), None);
+
+        *g.get_mut((0, 0)).unwrap() = '9';
+        g[(1, 0)] = '8';
+        assert_eq!(g.row(0), &['9', '8', '3']);
+        assert!(g.get_mut((-1, 0)).is_none());
+    }
+
+    #[test]
+    fn wrapping() {
+        let g = grid("123\n456\n");
+        assert_eq!(*g.get_wrapping((3, 0)), '1');
+        assert_eq!(*g.get_wrapping((-1, 0)), '3');
+        assert_eq!(*g.get_wrapping((-1, -1)), '6');
+        assert_eq!(*g.get_wrapping((7, 5)), '5');
+    }
+
+    #[test]
+    fn steps() {
+        let g = Grid::new(3, 2, 0);
+        assert_eq!(g.step((1, 0), (1, 0)), Some((2, 0)));
+        assert_eq!(g.step((1, 0), (0, 1)), Some((1, 1)));
+        assert_eq!(g.step((2, 0), (1, 0)), None);
+        assert_eq!(g.step((0, 0), (0, -1)), None);
+
+        let empty: Grid<u8> = Grid::from_rows(Vec::new());
+        assert_eq!(empty.step((0, 0), (1, 0)), None);
+    }
+
+    #[test]
+    fn neighbours() {
+        let g = Grid::new(3, 3, 0);
+
+        let mut n: Vec<_> = g.neighbours4((0, 0)).collect();
+        n.sort();
+        assert_eq!(n, vec![(0, 1), (1, 0)]);
+
+        assert_eq!(g.neighbours4((1, 1)).count(), 4);
+        assert_eq!(g.neighbours8((1, 1)).count(), 8);
+        assert_eq!(g.neighbours8((2, 2)).count(), 3);
+        assert_eq!(g.neighbours8((1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let g = grid("123\n456\n");
+        let rows: Vec<&[char]> = g.rows().collect();
+        assert_eq!(rows, vec![&['1', '2', '3'], &['4', '5', '6']]);
+
+        assert_eq!(g.col(1).copied().collect::<String>(), "25");
+        assert_eq!(g.col(2).rev().copied().collect::<String>(), "63");
+    }
+
+    #[test]
+    fn rotations() {
+        let g = grid("123\n456\n");
+        assert_eq!(g.transpose().to_string(), "14\n25\n36\n");
+        assert_eq!(g.rotate_cw().to_string(), "41\n52\n63\n");
+        assert_eq!(g.rotate_ccw().to_string(), "36\n25\n14\n");
+
+        assert_eq!(g.transpose().transpose(), g);
+        assert_eq!(g.rotate_cw().rotate_ccw(), g);
+        assert_eq!(g.rotate_cw().rotate_cw().rotate_cw().rotate_cw(), g);
+    }
+
+    #[test]
+    fn find_cell() {
+        let g = grid("...\n..S\n");
+        assert_eq!(g.find(&'S'), Some((2, 1)));
+        assert_eq!(g.find(&'#'), None);
+
+        let positions: Vec<_> = g.iter().filter(|(_, c)| **c == '.').map(|(p, _)| p).collect();
+        assert_eq!(positions, vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn render_overlay() {
+        let g = grid("...\n.#.\n");
+        let s = g.render(|pos, c| if pos == (0, 1) { '@' } else { *c });
+        assert_eq!(s, "...\n@#.\n");
+
+        let counts = g.map(|c| if *c == '#' { 1 } else { 0 });
+        assert_eq!(counts.to_string(), "000\n010\n");
+    }
+}
@@ -0,0 +1,296 @@
+//! Sets of numbers stored as ranges, for puzzles where there are far too many
+//! values to handle one at a time.
+//!
+//! An `IntervalSet` is kept normalised: its ranges are sorted, non-empty, and
+//! never overlap or touch, so two sets with the same members compare equal.
+//! A `Hyperrectangle` is the product of one set per dimension.
+
+use std::ops::Range;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Copy + Ord> Default for IntervalSet<T> {
+    fn default() -> Self {
+        return IntervalSet::new();
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ranges: Vec<Range<T>> = iter.into_iter()
+            .filter(|r| r.start < r.end)
+            .collect();
+        ranges.sort_by_key(|r| r.start);
+
+        // Merge anything overlapping or touching
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end => {
+                    last.end = last.end.max(r.end);
+                },
+                _ => merged.push(r),
+            }
+        }
+
+        return IntervalSet{ ranges: merged };
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(r: Range<T>) -> Self {
+        return IntervalSet::from_iter([r]);
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        return IntervalSet{ ranges: Vec::new() };
+    }
+
+    // The normalised ranges, in order
+    pub fn ranges(&self) -> &[Range<T>] {
+        return &self.ranges;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.ranges.is_empty();
+    }
+
+    pub fn contains(&self, v: &T) -> bool {
+        // The first range which ends after v is the only one that could have it
+        let i = self.ranges.partition_point(|r| r.end <= *v);
+        return self.ranges.get(i).is_some_and(|r| r.start <= *v);
+    }
+
+    pub fn min(&self) -> Option<T> {
+        return self.ranges.first().map(|r| r.start);
+    }
+
+    // One past the largest member
+    pub fn end(&self) -> Option<T> {
+        return self.ranges.last().map(|r| r.end);
+    }
+
+    pub fn insert(&mut self, r: Range<T>) {
+        *self = self.ranges.iter().cloned().chain([r]).collect();
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        return self.ranges.iter().chain(other.ranges.iter()).cloned().collect();
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut out = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                out.push(start..end);
+            }
+
+            // Whichever finishes first can't overlap anything else
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        // Already sorted and disjoint
+        return IntervalSet{ ranges: out };
+    }
+
+    // Everything in self which isn't in other
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut out = Vec::new();
+        let mut j = 0;
+
+        for r in &self.ranges {
+            let mut start = r.start;
+
+            // Skip the ones entirely before this range
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < r.end {
+                let hole = &other.ranges[k];
+                if hole.start > start {
+                    out.push(start..hole.start);
+                }
+                start = start.max(hole.end);
+                k += 1;
+            }
+
+            if start < r.end {
+                out.push(start..r.end);
+            }
+        }
+
+        return IntervalSet{ ranges: out };
+    }
+
+    // Splits into the members below `at`, and those at or above it
+    pub fn split_at(&self, at: T) -> (IntervalSet<T>, IntervalSet<T>) {
+        let mut below = Vec::new();
+        let mut above = Vec::new();
+
+        for r in &self.ranges {
+            if r.end <= at {
+                below.push(r.clone());
+            } else if r.start >= at {
+                above.push(r.clone());
+            } else {
+                below.push(r.start..at);
+                above.push(at..r.end);
+            }
+        }
+
+        return (IntervalSet{ ranges: below }, IntervalSet{ ranges: above });
+    }
+}
+
+impl<T: Copy + Ord + Into<i128>> IntervalSet<T> {
+    // The number of members
+    pub fn len(&self) -> u128 {
+        return self.ranges.iter()
+            .map(|r| (r.end.into() - r.start.into()) as u128)
+            .sum();
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Hyperrectangle<T, const N: usize> {
+    pub dims: [IntervalSet<T>; N],
+}
+
+impl<T: Copy + Ord, const N: usize> Hyperrectangle<T, N> {
+    pub fn new(ranges: [Range<T>; N]) -> Hyperrectangle<T, N> {
+        return Hyperrectangle{ dims: ranges.map(IntervalSet::from) };
+    }
+
+    pub fn empty() -> Hyperrectangle<T, N> {
+        return Hyperrectangle{ dims: std::array::from_fn(|_| IntervalSet::new()) };
+    }
+
+    // Empty if any side is
+    pub fn is_empty(&self) -> bool {
+        return self.dims.iter().any(|d| d.is_empty());
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        return self.dims.iter().zip(point).all(|(d, v)| d.contains(v));
+    }
+
+    pub fn intersection(&self, other: &Hyperrectangle<T, N>) -> Hyperrectangle<T, N> {
+        let mut dims = self.dims.clone();
+        for (d, o) in dims.iter_mut().zip(&other.dims) {
+            *d = d.intersection(o);
+        }
+
+        return Hyperrectangle{ dims };
+    }
+
+    // Cuts across dimension `dim`, giving the parts below `at`, and at or
+    // above it
+    pub fn split_at(&self, dim: usize, at: T) -> (Hyperrectangle<T, N>, Hyperrectangle<T, N>) {
+        let (below, above) = self.dims[dim].split_at(at);
+
+        let mut lo = self.clone();
+        let mut hi = self.clone();
+        lo.dims[dim] = below;
+        hi.dims[dim] = above;
+
+        return (lo, hi);
+    }
+}
+
+impl<T: Copy + Ord + Into<i128>, const N: usize> Hyperrectangle<T, N> {
+    // The number of points inside
+    pub fn volume(&self) -> u128 {
+        return self.dims.iter().map(|d| d.len()).product();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<u32>]) -> IntervalSet<u32> {
+        return ranges.iter().cloned().collect();
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn normalise() {
+        let s = set(&[5..8, 1..3, 2..4, 8..9, 12..12, 20..15]);
+        assert_eq!(s.ranges(), &[1..4, 5..9]);
+        assert_eq!(s.len(), 7);
+        assert_eq!((s.min(), s.end()), (Some(1), Some(9)));
+
+        assert!(IntervalSet::from(3..3).is_empty());
+        assert_eq!(IntervalSet::<u32>::new().len(), 0);
+    }
+
+    #[test]
+    fn membership() {
+        let s = set(&[1..4, 6..7]);
+        let members: Vec<u32> = (0..10).filter(|v| s.contains(v)).collect();
+        assert_eq!(members, vec![1, 2, 3, 6]);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[0..10, 20..30]);
+        let b = IntervalSet::from(5..25);
+
+        assert_eq!(a.union(&b), IntervalSet::from(0..30));
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..30]);
+        assert_eq!(b.difference(&a), IntervalSet::from(10..20));
+
+        let mut c = a.clone();
+        c.insert(10..20);
+        assert_eq!(c, IntervalSet::from(0..30));
+    }
+
+    #[test]
+    fn splitting() {
+        let s = set(&[0..10, 20..30]);
+        let (lo, hi) = s.split_at(25);
+        assert_eq!(lo.ranges(), &[0..10, 20..25]);
+        assert_eq!(hi, IntervalSet::from(25..30));
+
+        let (lo, hi) = s.split_at(10);
+        assert_eq!(lo, IntervalSet::from(0..10));
+        assert_eq!(hi, IntervalSet::from(20..30));
+    }
+
+    #[test]
+    fn boxes() {
+        let b = Hyperrectangle::new([1..4001, 1..4001, 1..4001, 1..4001]);
+        assert_eq!(b.volume(), 4000u128.pow(4));
+        assert!(b.contains(&[1, 2, 3, 4000]));
+        assert!(!b.contains(&[1, 2, 3, 4001]));
+
+        let (lo, hi) = b.split_at(2, 1000);
+        assert_eq!(lo.volume() + hi.volume(), b.volume());
+        assert_eq!(lo.volume(), 4000u128.pow(3) * 999);
+        assert!(lo.intersection(&hi).is_empty());
+
+        let (lo, hi) = b.split_at(0, 1);
+        assert!(lo.is_empty() && lo.volume() == 0);
+        assert_eq!(hi, b);
+
+        assert!(Hyperrectangle::<u32, 3>::empty().is_empty());
+    }
+}
@@ -0,0 +1,52 @@
+//! Just enough JSON to write out results for other tools to read.
+
+// Quotes and escapes `s` as a JSON string
+pub fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+
+    return out;
+}
+
+// Writes an object from (key, value) pairs, where the values are already JSON
+pub fn object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields.iter()
+        .map(|(k, v)| format!("{}:{}", string(k), v))
+        .collect();
+
+    return format!("{{{}}}", fields.join(","));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_strings() {
+        assert_eq!(string("abc"), "\"abc\"");
+        assert_eq!(string("a \"b\"\n\\"), "\"a \\\"b\\\"\\n\\\\\"");
+        assert_eq!(string("\x01"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn objects() {
+        assert_eq!(object(&[]), "{}");
+        assert_eq!(
+            object(&[("day", "1".to_string()), ("answer", string("x"))]),
+            "{\"day\":1,\"answer\":\"x\"}"
+        );
+    }
+}
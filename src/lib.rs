@@ -0,0 +1,29 @@
+//! Shared helpers for the Advent of Code 2023 solutions.
+//!
+//! These started life copy-pasted between the `NN/main.rs` binaries, so they
+//! live here now and the days use them from the `aoc` crate instead.
+//!
+//! The solutions themselves are in `days`, each implementing
+//! `solution::Solution`, and `runner` is the command line front-end for the
+//! `aoc` binary which can run any of them.
+
+pub mod aho;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod cycle;
+pub mod days;
+pub mod direction;
+pub mod dot;
+pub mod error;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod json;
+pub mod mockserver;
+pub mod numtheory;
+pub mod picture;
+pub mod prop;
+pub mod runner;
+pub mod solution;
@@ -0,0 +1,259 @@
+//! A stand-in for adventofcode.com on localhost, so that `client` can be
+//! tested without a network or a real session.
+//!
+//! It serves inputs and checks answers for whichever days it's given, replying
+//! with the same messages as the real site, including making you wait after a
+//! wrong answer.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::client::YEAR;
+use crate::solution::Part;
+
+struct State {
+    session: String,
+    inputs: HashMap<u32, String>,
+    answers: HashMap<(u32, Part), String>,
+    solved: HashSet<(u32, Part)>,
+    // How long to lock out answers for after a wrong one
+    wait: Duration,
+    locked_until: Option<Instant>,
+    // "METHOD path" of every request, in order
+    requests: Vec<String>,
+}
+
+pub struct MockServer {
+    pub url: String,
+    state: Arc<Mutex<State>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    cookie: Option<String>,
+    body: String,
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut fields = line.split_whitespace();
+    let method = fields.next()?.to_string();
+    let path = fields.next()?.to_string();
+
+    let mut cookie = None;
+    let mut content_len = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        let (name, value) = line.split_once(':')?;
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => {
+                cookie = value.trim().strip_prefix("session=").map(|s| s.to_string());
+            },
+            "content-length" => {
+                content_len = value.trim().parse().ok()?;
+            },
+            _ => {},
+        }
+    }
+
+    let mut body = vec![0; content_len];
+    reader.read_exact(&mut body).ok()?;
+
+    return Some(Request{ method, path, cookie, body: String::from_utf8_lossy(&body).to_string() });
+}
+
+fn respond(mut stream: &TcpStream, status: u32, body: &str) {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Error",
+    };
+    let resp = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, reason, body.len(), body
+    );
+    let _ = stream.write_all(resp.as_bytes());
+}
+
+fn article(day: u32, msg: &str) -> String {
+    return format!("<html><body><main><article><p>{} <a href=\"/{}/day/{}\">[Return to Day {}]</a></p></article></main></body></html>",
+        msg, YEAR, day, day);
+}
+
+fn format_wait(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    if secs >= 60 {
+        return format!("{}m {}s", secs / 60, secs % 60);
+    }
+
+    return format!("{}s", secs);
+}
+
+// "/2023/day/7/input" -> (7, "input")
+fn parse_path(path: &str) -> Option<(u32, &str)> {
+    let rest = path.strip_prefix(&format!("/{}/day/", YEAR))?;
+    let (day, what) = rest.split_once('/')?;
+
+    return Some((day.parse().ok()?, what));
+}
+
+fn check_answer(state: &mut State, day: u32, form: &str) -> String {
+    let mut level = None;
+    let mut answer = None;
+    for field in form.split('&') {
+        match field.split_once('=') {
+            Some(("level", v)) => level = v.parse::<Part>().ok(),
+            Some(("answer", v)) => answer = Some(v.to_string()),
+            _ => {},
+        }
+    }
+
+    let (part, answer) = match (level, answer) {
+        (Some(part), Some(answer)) => (part, answer),
+        _ => return article(day, "That's not a valid submission."),
+    };
+
+    let now = Instant::now();
+    if let Some(until) = state.locked_until.filter(|&until| until > now) {
+        return article(day, &format!("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {} left to wait.",
+            format_wait(until - now + Duration::from_millis(999))));
+    }
+
+    let expected = match state.answers.get(&(day, part)) {
+        Some(expected) => expected.clone(),
+        None => return article(day, "You don't seem to be solving the right level.  Did you already complete it?"),
+    };
+    if state.solved.contains(&(day, part)) || (part == Part::Two && !state.solved.contains(&(day, Part::One))) {
+        return article(day, "You don't seem to be solving the right level.  Did you already complete it?");
+    }
+
+    if answer == expected {
+        state.solved.insert((day, part));
+        return article(day, "That's the right answer!  You are <em>one gold star</em> closer to restoring snow operations.");
+    }
+
+    state.locked_until = Some(now + state.wait);
+
+    let hint = match (answer.parse::<i128>(), expected.parse::<i128>()) {
+        (Ok(a), Ok(e)) if a > e => "; your answer is too high",
+        (Ok(a), Ok(e)) if a < e => "; your answer is too low",
+        _ => "",
+    };
+    return article(day, &format!("That's not the right answer{}.  If you're stuck, make sure you're using the full input data.  Please wait {} before trying again.",
+        hint, format_wait(state.wait)));
+}
+
+fn handle(stream: TcpStream, state: &Mutex<State>) {
+    let req = match read_request(&stream) {
+        Some(req) => req,
+        None => return respond(&stream, 400, "Bad request"),
+    };
+
+    let mut state = state.lock().unwrap();
+    state.requests.push(format!("{} {}", req.method, req.path));
+
+    let (day, what) = match parse_path(&req.path) {
+        Some(p) => p,
+        None => return respond(&stream, 404, "404 Not Found"),
+    };
+
+    if req.cookie.as_deref() != Some(state.session.as_str()) {
+        if what == "input" {
+            return respond(&stream, 400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n");
+        }
+        return respond(&stream, 400, "Please log in.\n");
+    }
+
+    match (req.method.as_str(), what) {
+        ("GET", "input") => match state.inputs.get(&day) {
+            Some(input) => respond(&stream, 200, input),
+            None => respond(&stream, 404, "404 Not Found"),
+        },
+        ("POST", "answer") => {
+            let page = check_answer(&mut state, day, &req.body);
+            respond(&stream, 200, &page);
+        },
+        _ => respond(&stream, 404, "404 Not Found"),
+    }
+}
+
+impl MockServer {
+    // Listens on a free port on localhost, accepting `session` as the cookie
+    pub fn start(session: &str) -> std::io::Result<MockServer> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}", listener.local_addr()?);
+
+        let state = Arc::new(Mutex::new(State{
+            session: session.to_string(),
+            inputs: HashMap::new(),
+            answers: HashMap::new(),
+            solved: HashSet::new(),
+            wait: Duration::from_secs(60),
+            locked_until: None,
+            requests: Vec::new(),
+        }));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let state = state.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        handle(stream, &state);
+                    }
+                }
+            })
+        };
+
+        return Ok(MockServer{ url, state, stop, thread: Some(thread) });
+    }
+
+    pub fn add_day(&self, day: u32, input: &str, part1: &str, part2: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.inputs.insert(day, input.to_string());
+        state.answers.insert((day, Part::One), part1.to_string());
+        state.answers.insert((day, Part::Two), part2.to_string());
+    }
+
+    // How long answers are locked out for after a wrong one
+    pub fn set_wait(&self, wait: Duration) {
+        self.state.lock().unwrap().wait = wait;
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        return self.state.lock().unwrap().requests.clone();
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        // The accept() needs waking up to notice it should stop
+        self.stop.store(true, Ordering::SeqCst);
+        let _ = TcpStream::connect(self.url.trim_start_matches("http://"));
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Number theory helpers.
+//!
+//! Mostly for finding when several cycles of different lengths (and phases)
+//! line up, which comes up more than you'd think.
+
+use std::ops::{Div, Rem};
+
+pub trait Unsigned: Copy + Eq + Rem<Output = Self> + Div<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Unsigned for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+unsigned!(u32, u64, u128, usize);
+
+pub fn gcd<T: Unsigned>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+
+    return a;
+}
+
+// None if the result doesn't fit in T
+pub fn lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+
+    return (a / gcd(a, b)).checked_mul(b);
+}
+
+// The lcm of everything in `vals`, or None on overflow
+pub fn lcm_all<T: Unsigned, I: IntoIterator<Item = T>>(vals: I) -> Option<T> {
+    return vals.into_iter().try_fold(T::ONE, lcm);
+}
+
+// Returns (g, x, y) with g = gcd(a, b) = a*x + b*y, and g >= 0
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        return (-old_r, -old_x, -old_y);
+    }
+
+    return (old_r, old_x, old_y);
+}
+
+// The inverse of a modulo m, if they're coprime
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    if g != 1 {
+        return None;
+    }
+
+    return Some(x.rem_euclid(m));
+}
+
+// Solves x = r_i (mod m_i) for every (r_i, m_i) in `congruences`. The moduli
+// needn't be coprime. Returns (r, m) such that the solutions are exactly
+// r + k*m, with 0 <= r < m, or None if there aren't any (or it overflows).
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    let mut result: (i128, i128) = (0, 1);
+
+    for &(r2, m2) in congruences {
+        assert!(m2 > 0, "moduli must be positive");
+
+        let (r1, m1) = result;
+        let r2 = r2.rem_euclid(m2);
+
+        let (g, p, _) = ext_gcd(m1, m2);
+        let diff = r2 - r1;
+        if diff % g != 0 {
+            return None;
+        }
+
+        // x = r1 + m1 * k, where m1 * k = diff (mod m2), so
+        // k = (diff / g) * (m1 / g)^-1 (mod m2 / g), and p is that inverse
+        let step = m2 / g;
+        let k = (diff / g % step).checked_mul(p % step)?.rem_euclid(step);
+        let m = m1.checked_mul(step)?;
+        let r = r1.checked_add(m1.checked_mul(k)?)?.rem_euclid(m);
+
+        result = (r, m);
+    }
+
+    return Some(result);
+}
+
+// The smallest x >= `min` with x = r (mod m)
+pub fn first_at_least(r: i128, m: i128, min: i128) -> i128 {
+    return min + (r - min).rem_euclid(m);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_lcm() {
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(17u64, 5), 1);
+        assert_eq!(gcd(0u64, 5), 5);
+        assert_eq!(lcm(4u64, 6), Some(12));
+        assert_eq!(lcm(0u64, 6), Some(0));
+
+        // Cycle lengths which share one prime factor, like days 08 and 20
+        assert_eq!(lcm_all([3u64 * 7, 5 * 7, 11 * 7]), Some(3 * 5 * 7 * 11));
+        // And ones that don't
+        assert_eq!(lcm_all([4u64, 6, 10]), Some(60));
+        assert_eq!(lcm_all(Vec::<u64>::new()), Some(1));
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(lcm(u64::MAX as u128, u64::MAX as u128 - 1),
+            Some(u64::MAX as u128 * (u64::MAX as u128 - 1)));
+        assert_eq!(lcm_all([u32::MAX, 2, 3]), None);
+    }
+
+    #[test]
+    fn extended_euclid() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (7, 0), (0, 7), (13, 13)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i128);
+            assert_eq!(a * x + b * y, g);
+        }
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 10), None);
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        // The classic coprime one
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+
+        // Moduli sharing a factor
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+
+        // Negative and out of range residues
+        assert_eq!(crt(&[(-1, 4), (9, 6)]), Some((3, 12)));
+
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn crt_brute_force() {
+        for m1 in 1..12 {
+            for m2 in 1..12 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let expected = (0..m1 * m2).find(|x| x % m1 == r1 && x % m2 == r2);
+                        let got = crt(&[(r1, m1), (r2, m2)]);
+                        assert_eq!(got.map(|(r, _)| r), expected, "{} mod {}, {} mod {}", r1, m1, r2, m2);
+                        if let Some((_, m)) = got {
+                            assert_eq!(m as u64, lcm(m1 as u64, m2 as u64).unwrap());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn crt_overflow() {
+        let p = (1i128 << 61) - 1;
+        let q = (1i128 << 89) - 1;
+        assert_eq!(crt(&[(1, p), (2, q), (3, 7)]), None);
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(first_at_least(2, 5, 0), 2);
+        assert_eq!(first_at_least(2, 5, 3), 7);
+        assert_eq!(first_at_least(2, 5, 7), 7);
+        assert_eq!(first_at_least(-3, 5, 10), 12);
+    }
+}
@@ -0,0 +1,308 @@
+//! Drawing the grid puzzles, with things the solvers found (paths, energized
+//! cells, distances) laid over the top, for seeing what they're up to.
+//!
+//! A `Picture` can be shown in the terminal with ANSI colours or saved as a
+//! PPM image, and a list of them makes the frames of an animation:
+//!
+//! ```text
+//! cargo run --release --bin day17 -- 17/input.txt --draw 17.ppm
+//! cargo run --release --bin day14 -- 14/input.txt --frames -
+//! ```
+
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::direction::Direction;
+use crate::grid::Grid;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+pub const BLACK: Rgb = Rgb(0, 0, 0);
+pub const WHITE: Rgb = Rgb(255, 255, 255);
+pub const GREY: Rgb = Rgb(128, 128, 128);
+pub const RED: Rgb = Rgb(220, 40, 40);
+pub const GREEN: Rgb = Rgb(40, 160, 40);
+pub const BLUE: Rgb = Rgb(40, 80, 220);
+pub const YELLOW: Rgb = Rgb(240, 200, 0);
+
+// Low to high, for heatmaps
+const RAMP: [Rgb; 4] = [Rgb(0, 0, 96), Rgb(0, 160, 160), Rgb(240, 200, 0), Rgb(220, 30, 0)];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cell {
+    pub ch: char,
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+}
+
+impl Cell {
+    // The colour of the cell's pixels in an image, where anything drawn on top
+    // wins over the background. Without any colours, the empty looking
+    // characters are black and everything else is grey.
+    fn pixel(&self) -> Rgb {
+        if let Some(fg) = self.fg {
+            return fg;
+        }
+        if let Some(bg) = self.bg {
+            return bg;
+        }
+
+        return if self.ch == '.' || self.ch == ' ' { BLACK } else { GREY };
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Picture {
+    title: String,
+    cells: Grid<Cell>,
+}
+
+// Somewhere between `lo` and `hi`, for `t` from 0 to 1
+fn blend(lo: Rgb, hi: Rgb, t: f64) -> Rgb {
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    return Rgb(mix(lo.0, hi.0), mix(lo.1, hi.1), mix(lo.2, hi.2));
+}
+
+// The heatmap colour for `t` from 0 (coldest) to 1 (hottest)
+pub fn heat(t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0) * (RAMP.len() - 1) as f64;
+    let i = (t as usize).min(RAMP.len() - 2);
+    return blend(RAMP[i], RAMP[i + 1], t - i as f64);
+}
+
+impl Picture {
+    // Draws `grid` with `f` choosing the character for each cell, like
+    // Grid::render(), ready to have things drawn over it
+    pub fn new<T, F: Fn((usize, usize), &T) -> char>(grid: &Grid<T>, f: F) -> Picture {
+        let mut cells = grid.map(|_| Cell{ ch: ' ', fg: None, bg: None });
+        for (pos, v) in grid.iter() {
+            cells[pos].ch = f(pos, v);
+        }
+
+        return Picture{ title: String::new(), cells };
+    }
+
+    pub fn width(&self) -> usize {
+        return self.cells.width();
+    }
+
+    pub fn height(&self) -> usize {
+        return self.cells.height();
+    }
+
+    pub fn cell(&self, pos: (usize, usize)) -> &Cell {
+        return &self.cells[pos];
+    }
+
+    // Shown above it in the terminal, and as a comment in images
+    pub fn title(&mut self, title: &str) -> &mut Picture {
+        self.title = title.to_string();
+        return self;
+    }
+
+    pub fn colour<I: IntoIterator<Item = (usize, usize)>>(&mut self, cells: I, fg: Rgb) -> &mut Picture {
+        for pos in cells {
+            self.cells[pos].fg = Some(fg);
+        }
+        return self;
+    }
+
+    pub fn shade<I: IntoIterator<Item = (usize, usize)>>(&mut self, cells: I, bg: Rgb) -> &mut Picture {
+        for pos in cells {
+            self.cells[pos].bg = Some(bg);
+        }
+        return self;
+    }
+
+    // Replaces the characters too
+    pub fn mark<I: IntoIterator<Item = (usize, usize)>>(&mut self, cells: I, ch: char, fg: Rgb) -> &mut Picture {
+        for pos in cells {
+            self.cells[pos] = Cell{ ch, fg: Some(fg), ..self.cells[pos] };
+        }
+        return self;
+    }
+
+    // Draws arrows along `path` showing which way it goes from each cell. Any
+    // jumps between cells which aren't next to each other are only coloured.
+    pub fn path(&mut self, path: &[(usize, usize)], fg: Rgb) -> &mut Picture {
+        for (i, &pos) in path.iter().enumerate() {
+            let next = path.get(i + 1).and_then(|&(x, y)| {
+                let delta = (x as i64 - pos.0 as i64, y as i64 - pos.1 as i64);
+                return Direction::iter().find(|d| (d.delta().0 as i64, d.delta().1 as i64) == delta);
+            });
+
+            let cell = &mut self.cells[pos];
+            if let Some(dir) = next {
+                cell.ch = dir.arrow();
+            }
+            cell.fg = Some(fg);
+        }
+        return self;
+    }
+
+    // Shades each cell by its value, scaled between the smallest and largest
+    pub fn heatmap<I: IntoIterator<Item = ((usize, usize), u64)>>(&mut self, values: I) -> &mut Picture {
+        let values: Vec<((usize, usize), u64)> = values.into_iter().collect();
+        let lo = values.iter().map(|&(_, v)| v).min().unwrap_or(0);
+        let hi = values.iter().map(|&(_, v)| v).max().unwrap_or(0);
+
+        for (pos, v) in values {
+            let t = if hi == lo { 0.0 } else { (v - lo) as f64 / (hi - lo) as f64 };
+            self.cells[pos].bg = Some(heat(t));
+        }
+        return self;
+    }
+
+    // For a terminal with 24-bit colour, only changing colour when it needs to
+    pub fn ansi(&self) -> String {
+        let mut out = String::new();
+        if !self.title.is_empty() {
+            out.push_str(&self.title);
+            out.push('\n');
+        }
+
+        for row in self.cells.rows() {
+            let mut current = (None, None);
+            for cell in row {
+                if (cell.fg, cell.bg) != current {
+                    out.push_str("\x1b[0m");
+                    if let Some(Rgb(r, g, b)) = cell.fg {
+                        out.push_str(&format!("\x1b[38;2;{};{};{}m", r, g, b));
+                    }
+                    if let Some(Rgb(r, g, b)) = cell.bg {
+                        out.push_str(&format!("\x1b[48;2;{};{};{}m", r, g, b));
+                    }
+                    current = (cell.fg, cell.bg);
+                }
+                out.push(cell.ch);
+            }
+
+            if current != (None, None) {
+                out.push_str("\x1b[0m");
+            }
+            out.push('\n');
+        }
+
+        return out;
+    }
+
+    // A binary PPM image with each cell as a `scale` x `scale` square
+    pub fn ppm(&self, scale: usize) -> Vec<u8> {
+        let (width, height) = (self.width() * scale, self.height() * scale);
+
+        let mut out = Vec::with_capacity(width * height * 3 + 64);
+        out.extend_from_slice(b"P6\n");
+        // Comments end at the line break, so there can't be any in it
+        for line in self.title.lines() {
+            out.extend_from_slice(format!("# {}\n", line).as_bytes());
+        }
+        out.extend_from_slice(format!("{} {}\n255\n", width, height).as_bytes());
+
+        for row in self.cells.rows() {
+            let mut line = Vec::with_capacity(width * 3);
+            for cell in row {
+                let Rgb(r, g, b) = cell.pixel();
+                for _ in 0..scale {
+                    line.extend_from_slice(&[r, g, b]);
+                }
+            }
+            for _ in 0..scale {
+                out.extend_from_slice(&line);
+            }
+        }
+
+        return out;
+    }
+}
+
+// Shows `frames` one after the other in the terminal (on stderr, to keep
+// stdout for the answers), clearing it in between
+pub fn play(frames: &[Picture], delay: Duration) -> io::Result<()> {
+    let mut stderr = io::stderr().lock();
+    for (i, frame) in frames.iter().enumerate() {
+        if i > 0 {
+            thread::sleep(delay);
+            write!(stderr, "\x1b[H\x1b[2J")?;
+        }
+        write!(stderr, "{}", frame.ansi())?;
+        stderr.flush()?;
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picture() -> Picture {
+        let grid = Grid::parse("#..\n...\n", "#..\n...\n", "#.").unwrap();
+        return Picture::new(&grid, |_, &c| c);
+    }
+
+    #[test]
+    fn overlays() {
+        let mut pic = picture();
+        pic.path(&[(0, 1), (1, 1), (1, 0)], RED)
+            .shade([(2, 0)], BLUE)
+            .mark([(2, 1)], 'O', WHITE);
+
+        assert_eq!(pic.cell((0, 1)), &Cell{ ch: '>', fg: Some(RED), bg: None });
+        assert_eq!(pic.cell((1, 1)), &Cell{ ch: '^', fg: Some(RED), bg: None });
+        // The end of the path keeps its character
+        assert_eq!(pic.cell((1, 0)), &Cell{ ch: '.', fg: Some(RED), bg: None });
+        assert_eq!(pic.cell((2, 0)), &Cell{ ch: '.', fg: None, bg: Some(BLUE) });
+        assert_eq!(pic.cell((2, 1)), &Cell{ ch: 'O', fg: Some(WHITE), bg: None });
+        assert_eq!(pic.cell((0, 0)), &Cell{ ch: '#', fg: None, bg: None });
+    }
+
+    #[test]
+    fn heatmap() {
+        let mut pic = picture();
+        pic.heatmap([((0, 0), 10), ((1, 0), 15), ((2, 0), 20)]);
+
+        assert_eq!(pic.cell((0, 0)).bg, Some(RAMP[0]));
+        assert_eq!(pic.cell((2, 0)).bg, Some(RAMP[3]));
+        assert_eq!(pic.cell((1, 0)).bg, Some(blend(RAMP[1], RAMP[2], 0.5)));
+        assert_eq!(pic.cell((0, 1)).bg, None);
+
+        // All the same value is all cold
+        let mut pic = picture();
+        pic.heatmap([((0, 0), 3), ((1, 1), 3)]);
+        assert_eq!(pic.cell((1, 1)).bg, Some(RAMP[0]));
+    }
+
+    #[test]
+    fn ansi() {
+        let mut pic = picture();
+        pic.title("test").colour([(1, 0), (2, 0)], RED);
+
+        assert_eq!(pic.ansi(), concat!(
+            "test\n",
+            "#\x1b[0m\x1b[38;2;220;40;40m..\x1b[0m\n",
+            "...\n",
+        ));
+    }
+
+    #[test]
+    fn ppm() {
+        let mut pic = picture();
+        pic.title("two\nlines").shade([(1, 0)], BLUE);
+
+        let image = pic.ppm(2);
+        let header = b"P6\n# two\n# lines\n6 4\n255\n";
+        assert_eq!(&image[..header.len()], header);
+
+        let pixels = &image[header.len()..];
+        assert_eq!(pixels.len(), 6 * 4 * 3);
+        // Both rows of pixels for the first row of cells are grey, blue, black
+        let row = [GREY, GREY, BLUE, BLUE, BLACK, BLACK].iter()
+            .flat_map(|c| [c.0, c.1, c.2])
+            .collect::<Vec<u8>>();
+        assert_eq!(&pixels[..18], &row[..]);
+        assert_eq!(&pixels[18..36], &row[..]);
+        assert!(pixels[36..].iter().all(|&v| v == 0));
+    }
+}
@@ -0,0 +1,254 @@
+//! Randomised property testing, without pulling in a crate for it.
+//!
+//! `check()` runs a property over lots of generated cases, and when one fails
+//! it shrinks it down to something small enough to make sense of before
+//! giving up. It's mostly for checking the clever solutions against dumb but
+//! obviously right ones on small inputs, in `tests/differential.rs`, and the
+//! interval sets against plain sets of numbers in `tests/interval.rs`.
+//!
+//! The cases come from a fixed seed so failures can be reproduced, which can
+//! be changed with `$AOC_SEED` to go looking for new ones.
+
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+const DEFAULT_SEED: u64 = 0x5eed;
+
+// Gives up shrinking after this many steps, and reports what it has so far
+const MAX_SHRINKS: usize = 1000;
+
+// xorshift, which is plenty random enough for making test cases
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // It gets stuck on 0
+        return Rng(if seed == 0 { DEFAULT_SEED } else { seed });
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        return self.0;
+    }
+
+    // From 0 up to, but not including, `n`
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "nothing below 0");
+        return self.next_u64() % n;
+    }
+
+    // From `lo` to `hi` inclusive
+    pub fn between(&mut self, lo: u64, hi: u64) -> u64 {
+        return lo + self.below(hi - lo + 1);
+    }
+
+    pub fn one_in(&mut self, n: u64) -> bool {
+        return self.below(n) == 0;
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        return &items[self.below(items.len() as u64) as usize];
+    }
+}
+
+// Things which can be made simpler, to find the smallest case which still
+// fails
+pub trait Shrink: Sized {
+    // Simpler versions of self, with the simplest first
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! shrink_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Shrink for $t {
+                fn shrink(&self) -> Vec<Self> {
+                    let mut out = Vec::new();
+                    for v in [0, *self / 2, self.saturating_sub(1)] {
+                        if v < *self && !out.contains(&v) {
+                            out.push(v);
+                        }
+                    }
+                    return out;
+                }
+            }
+        )*
+    };
+}
+
+shrink_unsigned!(u8, u32, u64, usize);
+
+// Dropping elements, then shrinking each one in place
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut out = Vec::new();
+        for i in 0..self.len() {
+            let mut v = self.clone();
+            v.remove(i);
+            out.push(v);
+        }
+
+        for (i, item) in self.iter().enumerate() {
+            for smaller in item.shrink() {
+                let mut v = self.clone();
+                v[i] = smaller;
+                out.push(v);
+            }
+        }
+
+        return out;
+    }
+}
+
+impl<A: Shrink + Clone, B: Shrink + Clone> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<Self> {
+        let mut out: Vec<Self> = self.0.shrink().into_iter().map(|a| (a, self.1.clone())).collect();
+        out.extend(self.1.shrink().into_iter().map(|b| (self.0.clone(), b)));
+        return out;
+    }
+}
+
+impl<A: Shrink + Clone, B: Shrink + Clone, C: Shrink + Clone> Shrink for (A, B, C) {
+    fn shrink(&self) -> Vec<Self> {
+        let mut out: Vec<Self> = self.0.shrink().into_iter().map(|a| (a, self.1.clone(), self.2.clone())).collect();
+        out.extend(self.1.shrink().into_iter().map(|b| (self.0.clone(), b, self.2.clone())));
+        out.extend(self.2.shrink().into_iter().map(|c| (self.0.clone(), self.1.clone(), c)));
+        return out;
+    }
+}
+
+// Says what was different, for properties comparing two answers
+pub fn same<T: PartialEq + Debug>(fast: T, slow: T) -> Result<(), String> {
+    if fast != slow {
+        return Err(format!("got {:?}, but the brute force way got {:?}", fast, slow));
+    }
+
+    return Ok(());
+}
+
+fn seed() -> u64 {
+    return match std::env::var("AOC_SEED") {
+        Ok(s) => s.parse().unwrap_or_else(|_| panic!("invalid $AOC_SEED '{}'", s)),
+        Err(_) => DEFAULT_SEED,
+    };
+}
+
+// Panicking counts as failing, so that those get shrunk too
+fn holds<T, P: Fn(&T) -> Result<(), String>>(prop: &P, case: &T) -> Result<(), String> {
+    return match panic::catch_unwind(AssertUnwindSafe(|| prop(case))) {
+        Ok(result) => result,
+        Err(e) => {
+            let msg = e.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| e.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            Err(format!("panicked: {}", msg))
+        },
+    };
+}
+
+// Keeps taking the first simpler case which still fails, until there aren't
+// any. Returns the smallest one, why it fails, and how many steps it took.
+fn minimise<T: Shrink, P: Fn(&T) -> Result<(), String>>(case: T, error: String, prop: &P) -> (T, String, usize) {
+    let (mut case, mut error) = (case, error);
+
+    let mut steps = 0;
+    'shrink: while steps < MAX_SHRINKS {
+        for smaller in case.shrink() {
+            if let Err(e) = holds(prop, &smaller) {
+                (case, error) = (smaller, e);
+                steps += 1;
+                continue 'shrink;
+            }
+        }
+        break;
+    }
+
+    return (case, error, steps);
+}
+
+// Checks `prop` holds for `cases` cases made by `gen`, panicking with the
+// smallest failing case it can find if not
+pub fn check<T, G, P>(name: &str, cases: usize, mut gen: G, prop: P)
+where
+    T: Shrink + Debug,
+    G: FnMut(&mut Rng) -> T,
+    P: Fn(&T) -> Result<(), String>,
+{
+    let seed = seed();
+    let mut rng = Rng::new(seed);
+
+    for i in 0..cases {
+        let case = gen(&mut rng);
+        if let Err(error) = holds(&prop, &case) {
+            let (case, error, steps) = minimise(case, error, &prop);
+            panic!("{}: case {} from seed {} failed, and shrunk in {} steps to\n{:?}\n{}",
+                name, i, seed, steps, case, error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            let v = a.between(3, 7);
+            assert_eq!(v, b.between(3, 7));
+            assert!((3..=7).contains(&v));
+        }
+
+        assert_ne!(Rng::new(0).next_u64(), 0);
+    }
+
+    #[test]
+    fn shrink_values() {
+        assert_eq!(10u32.shrink(), vec![0, 5, 9]);
+        assert_eq!(1u32.shrink(), vec![0]);
+        assert!(0u32.shrink().is_empty());
+
+        assert_eq!(vec![2u8, 1].shrink(), vec![vec![1], vec![2], vec![0, 1], vec![1, 1], vec![2, 0]]);
+        assert_eq!((1u8, 2u8).shrink(), vec![(0, 2), (1, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn shrinks_to_smallest() {
+        // Fails once the sum gets to 100
+        let prop = |v: &Vec<u32>| if v.iter().sum::<u32>() < 100 { Ok(()) } else { Err("too big".to_string()) };
+
+        let (case, error, _) = minimise(vec![7, 80, 3, 95], "too big".to_string(), &prop);
+        assert_eq!(error, "too big");
+        // Which is as small as it can get one step at a time
+        assert_eq!(case.iter().sum::<u32>(), 100);
+        assert!(case.shrink().iter().all(|v| prop(v).is_ok()));
+    }
+
+    #[test]
+    fn shrinks_panics() {
+        let prop = |v: &(u32, u32)| {
+            assert!(v.0 < 10 || v.1 < 10, "both big");
+            return Ok(());
+        };
+
+        let (case, error, _) = minimise((50, 70), String::new(), &prop);
+        assert_eq!(case, (10, 10));
+        assert_eq!(error, "panicked: both big");
+    }
+
+    #[test]
+    fn check_passes() {
+        check("below", 100, |rng| rng.below(10), |v| same(*v < 10, true));
+    }
+
+    #[test]
+    #[should_panic(expected = "shrunk")]
+    fn check_fails() {
+        check("below", 100, |rng| rng.below(10), |v| same(*v < 5, true));
+    }
+}
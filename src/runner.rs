@@ -0,0 +1,929 @@
+//! Command line front-end shared by the `aoc` binary and the per-day ones.
+
+use std::fs;
+use std::io::{self, Read};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::answers::{self, Store, Verdict};
+use crate::bench;
+use crate::client::{Client, Hint, Outcome};
+use crate::days;
+use crate::error::ParseErr;
+use crate::json;
+use crate::picture::{self, Picture};
+use crate::solution::{Answer, Part, PartResult, Result, Run, Runner};
+
+const USAGE: &str = "usage: aoc run <day|all> [--part 1|2] [--input FILE|-]... [--format text|json]
+               [--dot FILE] [--draw FILE|-] [--frames DIR|-]
+       aoc bench <day|all> [--iters N] [--input FILE|-] [--save FILE] [--baseline FILE]
+       aoc verify <day|all> [--input FILE|-]... [--answers FILE] [--record]
+       aoc fetch <day|all>
+       aoc submit <day> <1|2> [ANSWER] [--answers FILE]";
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Format {
+    Text,
+    // One JSON object per line for each answer, and nothing else on stdout
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("invalid format '{}', expected text or json", s)),
+        }
+    }
+}
+
+// Input file name meaning "read standard input instead"
+pub const STDIN: &str = "-";
+
+// Where a day's puzzle input lives when one isn't given explicitly
+pub fn default_input(day: u32) -> String {
+    return format!("{:02}/input.txt", day);
+}
+
+fn get_runner(day: u32) -> Result<&'static dyn Runner> {
+    return days::get(day).ok_or_else(|| format!("day {} is not implemented", day).into());
+}
+
+pub fn read_input(fname: &str) -> Result<String> {
+    if fname == STDIN {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)
+            .map_err(|e| format!("<stdin>: {}", e))?;
+        return Ok(input);
+    }
+
+    return fs::read_to_string(fname).map_err(|e| format!("{}: {}", fname, e).into());
+}
+
+fn display_name(fname: &str) -> &str {
+    return if fname == STDIN { "<stdin>" } else { fname };
+}
+
+// Says which file a parse error came from, as well as where in it
+pub fn in_file(e: Box<dyn std::error::Error>, fname: &str) -> Box<dyn std::error::Error> {
+    return match e.downcast::<ParseErr>() {
+        Ok(e) => e.in_file(display_name(fname)).into(),
+        Err(e) => e,
+    };
+}
+
+// Pictures are made into images about this many pixels across at most
+const IMAGE_SIZE: usize = 1000;
+
+// Between frames when they're played in the terminal
+const FRAME_DELAY: Duration = Duration::from_millis(100);
+
+// File name meaning "show it in the terminal" for the pictures
+const TERMINAL: &str = "-";
+
+// The extra things which can be drawn while running a day, and where to
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct Drawing {
+    // The input as a graph
+    dot: Option<String>,
+    // The last picture of the solver, as an image or in the terminal
+    draw: Option<String>,
+    // All of them, into a directory of images or played in the terminal
+    frames: Option<String>,
+}
+
+impl Drawing {
+    fn set(&mut self, flag: &str, value: Option<String>) -> Result<()> {
+        let value = Some(value.ok_or_else(|| format!("{} needs a value", flag))?);
+        match flag {
+            "--dot" => self.dot = value,
+            "--draw" => self.draw = value,
+            "--frames" => self.frames = value,
+            _ => unreachable!(),
+        }
+
+        return Ok(());
+    }
+
+    // The first one asked for, to complain about
+    fn flag(&self) -> Option<&'static str> {
+        if self.dot.is_some() {
+            return Some("--dot");
+        } else if self.draw.is_some() {
+            return Some("--draw");
+        } else if self.frames.is_some() {
+            return Some("--frames");
+        }
+
+        return None;
+    }
+}
+
+// Draws the input as a graph in the file `dot`
+fn write_dot(runner: &dyn Runner, input: &str, fname: &str, dot: &str) -> Result<()> {
+    let graph = runner.dot(input).map_err(|e| in_file(e, fname))?
+        .ok_or_else(|| format!("day {} doesn't have a graph to draw", runner.day()))?;
+
+    return fs::write(dot, graph.to_string()).map_err(|e| format!("{}: {}", dot, e).into());
+}
+
+fn write_image(picture: &Picture, fname: &str) -> Result<()> {
+    let scale = std::cmp::max(1, IMAGE_SIZE / std::cmp::max(picture.width(), picture.height()));
+    return fs::write(fname, picture.ppm(scale)).map_err(|e| format!("{}: {}", fname, e).into());
+}
+
+// Draws the solver working on the input, with the last picture going to
+// `draw` and all of them to `frames`
+fn write_pictures(runner: &dyn Runner, input: &str, fname: &str, draw: Option<&str>, frames: Option<&str>) -> Result<()> {
+    let pictures = runner.draw(input).map_err(|e| in_file(e, fname))?;
+    let last = pictures.last()
+        .ok_or_else(|| format!("day {} doesn't have anything to draw", runner.day()))?;
+
+    match draw {
+        Some(TERMINAL) => eprint!("{}", last.ansi()),
+        Some(draw) => write_image(last, draw)?,
+        None => {},
+    }
+
+    match frames {
+        Some(TERMINAL) => picture::play(&pictures, FRAME_DELAY)?,
+        Some(dir) => {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir, e))?;
+            for (i, picture) in pictures.iter().enumerate() {
+                write_image(picture, &format!("{}/{:04}.ppm", dir, i))?;
+            }
+        },
+        None => {},
+    }
+
+    return Ok(());
+}
+
+// Also draws whatever `drawing` asks for
+fn run_file(runner: &dyn Runner, fname: &str, parts: &[Part], drawing: &Drawing) -> Result<Run> {
+    let input = read_input(fname)?;
+
+    if let Some(dot) = &drawing.dot {
+        write_dot(runner, &input, fname, dot)?;
+    }
+    if drawing.draw.is_some() || drawing.frames.is_some() {
+        write_pictures(runner, &input, fname, drawing.draw.as_deref(), drawing.frames.as_deref())?;
+    }
+
+    return runner.run(&input, parts).map_err(|e| in_file(e, fname));
+}
+
+// Prints errors in full and exits, rather than leaving it to main() which
+// would print their Debug form.
+pub fn report(result: Result<()>) -> Result<()> {
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+
+    return Ok(());
+}
+
+// `input` is only given when there's more than one to tell apart
+fn json_record(day: u32, result: &PartResult, answer: &Answer, input: Option<&str>) -> String {
+    let mut fields = vec![
+        ("day", day.to_string()),
+        ("part", result.part.to_string()),
+        ("answer", answer.to_json()),
+        ("elapsed_ns", result.elapsed.as_nanos().to_string()),
+    ];
+    if let Some(input) = input {
+        fields.push(("input", json::string(display_name(input))));
+    }
+
+    return json::object(&fields);
+}
+
+fn run_day(day: u32) -> Result<()> {
+    let mut fnames: Vec<String> = Vec::new();
+    let mut format = Format::Text;
+    let mut drawing = Drawing::default();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" | "-f" => {
+                format = args.next().ok_or("--format needs a value")?.parse()?;
+            },
+            "--dot" | "--draw" | "--frames" => {
+                drawing.set(&arg, args.next())?;
+            },
+            _ => fnames.push(arg),
+        }
+    }
+    if fnames.is_empty() {
+        fnames.push(STDIN.to_string());
+    }
+    if let Some(flag) = drawing.flag() {
+        if fnames.len() > 1 {
+            return Err(format!("{} can only be used with a single input", flag).into());
+        }
+    }
+
+    let runner = get_runner(day)?;
+
+    let mut failures = 0;
+    for fname in &fnames {
+        let several = fnames.len() > 1;
+        if several && format == Format::Text {
+            println!("{}:", display_name(fname));
+        }
+
+        // Carry on with the other files if one of them is bad
+        let run = match run_file(runner, fname, &Part::ALL, &drawing) {
+            Ok(run) => run,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                failures += 1;
+                continue;
+            },
+        };
+
+        for result in &run.parts {
+            match &result.answer {
+                Ok(answer) => match format {
+                    Format::Text => println!("{}", answer),
+                    Format::Json => {
+                        let input = if several { Some(fname.as_str()) } else { None };
+                        println!("{}", json_record(day, result, answer, input));
+                    },
+                },
+                Err(e) => {
+                    eprintln!("error: part {}: {}", result.part, e);
+                    failures += 1;
+                },
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!("{} failure(s)", failures).into());
+    }
+
+    return Ok(());
+}
+
+// Entry point for the `dayNN` binaries: runs both parts on each file given as
+// an argument (or standard input, for none or "-") and prints the answers one
+// per line, or as JSON with "--format json". "--dot FILE" also draws the
+// input as a graph, and "--draw" and "--frames" the solver at work, on the
+// days which can.
+pub fn day_main(day: u32) -> Result<()> {
+    return report(run_day(day));
+}
+
+struct RunArgs {
+    days: Vec<u32>,
+    parts: Vec<Part>,
+    // Empty for each day's default input
+    inputs: Vec<String>,
+    format: Format,
+    drawing: Drawing,
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs> {
+    let mut days = None;
+    let mut parts = Part::ALL.to_vec();
+    let mut inputs = Vec::new();
+    let mut format = Format::Text;
+    let mut drawing = Drawing::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let part = iter.next().ok_or("--part needs a value")?;
+                parts = vec![part.parse::<Part>()?];
+            },
+            "--input" | "-i" => {
+                inputs.push(iter.next().ok_or("--input needs a value")?.to_string());
+            },
+            "--format" | "-f" => {
+                format = iter.next().ok_or("--format needs a value")?.parse()?;
+            },
+            "--dot" | "--draw" | "--frames" => {
+                drawing.set(arg, iter.next().cloned())?;
+            },
+            _ if days.is_none() => {
+                days = Some(parse_days(arg)?);
+            },
+            _ => {
+                return Err(format!("unexpected argument '{}'\n{}", arg, USAGE).into());
+            },
+        }
+    }
+
+    let days: Vec<u32> = days.ok_or(USAGE)?;
+    if !inputs.is_empty() && days.len() > 1 {
+        return Err("--input can only be used when running a single day".into());
+    }
+    if let Some(flag) = drawing.flag() {
+        if days.len() > 1 || inputs.len() > 1 {
+            return Err(format!("{} can only be used with a single day and input", flag).into());
+        }
+    }
+
+    return Ok(RunArgs{ days, parts, inputs, format, drawing });
+}
+
+// A day number or "all"
+fn parse_days(arg: &str) -> Result<Vec<u32>> {
+    if arg == "all" {
+        return Ok(days::ALL.iter().map(|r| r.day()).collect());
+    }
+
+    let day = arg.parse::<u32>()
+        .map_err(|_| format!("invalid day '{}'", arg))?;
+    return Ok(vec![day]);
+}
+
+fn cmd_run(args: &[String]) -> Result<()> {
+    let args = parse_run_args(args)?;
+
+    let mut failures = 0;
+    let mut total = std::time::Duration::ZERO;
+
+    let mut jobs = Vec::new();
+    for &day in &args.days {
+        if args.inputs.is_empty() {
+            jobs.push((day, default_input(day)));
+        } else {
+            for fname in &args.inputs {
+                jobs.push((day, fname.to_string()));
+            }
+        }
+    }
+
+    for (day, fname) in &jobs {
+        let runner = get_runner(*day)?;
+
+        // Only say which file it was if there's more than one for the day
+        let name = if args.inputs.len() > 1 {
+            format!("day {:02} [{}]", day, display_name(fname))
+        } else {
+            format!("day {:02}", day)
+        };
+
+        let json = args.format == Format::Json;
+        let input = if args.inputs.len() > 1 { Some(fname.as_str()) } else { None };
+
+        // In JSON mode only the answers go to stdout, everything else is
+        // diagnostics
+        let run = match run_file(runner, fname, &args.parts, &args.drawing) {
+            Ok(run) => run,
+            Err(e) => {
+                eprintln!("{}: error: {}", name, e);
+                failures += 1;
+                continue;
+            },
+        };
+
+        if !json {
+            println!("{}: parse ({:?})", name, run.parse_elapsed);
+        }
+        total += run.parse_elapsed;
+
+        for result in &run.parts {
+            match &result.answer {
+                Ok(answer) if json => {
+                    println!("{}", json_record(*day, result, answer, input));
+                },
+                Ok(answer) => {
+                    println!("{} part {}: {} ({:?})", name, result.part, answer, result.elapsed);
+                },
+                Err(e) if json => {
+                    eprintln!("{} part {}: error: {}", name, result.part, e);
+                    failures += 1;
+                },
+                Err(e) => {
+                    eprintln!("{} part {}: error: {} ({:?})", name, result.part, e, result.elapsed);
+                    failures += 1;
+                },
+            }
+            total += result.elapsed;
+        }
+    }
+
+    if jobs.len() > 1 && args.format == Format::Text {
+        println!("total: {:?}", total);
+    }
+
+    if failures > 0 {
+        return Err(format!("{} failure(s)", failures).into());
+    }
+
+    return Ok(());
+}
+
+struct BenchArgs {
+    days: Vec<u32>,
+    iters: usize,
+    input: Option<String>,
+    save: Option<String>,
+    baseline: Option<String>,
+}
+
+fn parse_bench_args(args: &[String]) -> Result<BenchArgs> {
+    let mut days = None;
+    let mut iters = 10;
+    let mut input = None;
+    let mut save = None;
+    let mut baseline = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--iters" | "-n" => {
+                let n = iter.next().ok_or("--iters needs a value")?;
+                iters = n.parse::<usize>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("invalid number of iterations '{}'", n))?;
+            },
+            "--input" | "-i" => {
+                input = Some(iter.next().ok_or("--input needs a value")?.to_string());
+            },
+            "--save" => {
+                save = Some(iter.next().ok_or("--save needs a value")?.to_string());
+            },
+            "--baseline" => {
+                baseline = Some(iter.next().ok_or("--baseline needs a value")?.to_string());
+            },
+            _ if days.is_none() => {
+                days = Some(parse_days(arg)?);
+            },
+            _ => {
+                return Err(format!("unexpected argument '{}'\n{}", arg, USAGE).into());
+            },
+        }
+    }
+
+    let days: Vec<u32> = days.ok_or(USAGE)?;
+    if input.is_some() && days.len() > 1 {
+        return Err("--input can only be used when benchmarking a single day".into());
+    }
+
+    return Ok(BenchArgs{ days, iters, input, save, baseline });
+}
+
+fn cmd_bench(args: &[String]) -> Result<()> {
+    let args = parse_bench_args(args)?;
+
+    let baseline = match &args.baseline {
+        Some(fname) => Some(bench::load(fname)?),
+        None => None,
+    };
+
+    let mut failures = 0;
+    let mut benches = Vec::new();
+
+    for &day in &args.days {
+        let runner = get_runner(day)?;
+        let fname = args.input.clone().unwrap_or_else(|| default_input(day));
+
+        let result = read_input(&fname)
+            .and_then(|input| bench::bench(runner, &input, args.iters));
+        let b = match result {
+            Ok(b) => b,
+            Err(e) => {
+                eprintln!("day {:02}: error: {}", day, e);
+                failures += 1;
+                continue;
+            },
+        };
+
+        for (phase, stats) in &b.phases {
+            let mut line = format!(
+                "day {:02} {:<6} min {:>12?}  median {:>12?}  max {:>12?}  {:>10.2} MB/s",
+                day, phase.to_string(), stats.min, stats.median, stats.max,
+                stats.throughput(b.input_len),
+            );
+
+            let key = (day, phase.to_string());
+            if let Some(before) = baseline.as_ref().and_then(|bl| bl.get(&key)) {
+                line += &format!("  {:>8} vs baseline", bench::change(before, stats));
+            }
+
+            println!("{}", line);
+        }
+
+        benches.push(b);
+    }
+
+    if let Some(fname) = &args.save {
+        bench::save(fname, &benches)?;
+    }
+
+    if failures > 0 {
+        return Err(format!("{} failure(s)", failures).into());
+    }
+
+    return Ok(());
+}
+
+struct VerifyArgs {
+    days: Vec<u32>,
+    inputs: Vec<String>,
+    answers: String,
+    // Save answers for inputs which don't have any yet
+    record: bool,
+}
+
+fn parse_verify_args(args: &[String]) -> Result<VerifyArgs> {
+    let mut days = None;
+    let mut inputs = Vec::new();
+    let mut answers = answers::DEFAULT_FILE.to_string();
+    let mut record = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                inputs.push(iter.next().ok_or("--input needs a value")?.to_string());
+            },
+            "--answers" | "-a" => {
+                answers = iter.next().ok_or("--answers needs a value")?.to_string();
+            },
+            "--record" => {
+                record = true;
+            },
+            _ if days.is_none() => {
+                days = Some(parse_days(arg)?);
+            },
+            _ => {
+                return Err(format!("unexpected argument '{}'\n{}", arg, USAGE).into());
+            },
+        }
+    }
+
+    let days: Vec<u32> = days.ok_or(USAGE)?;
+    if !inputs.is_empty() && days.len() > 1 {
+        return Err("--input can only be used when verifying a single day".into());
+    }
+
+    return Ok(VerifyArgs{ days, inputs, answers, record });
+}
+
+fn cmd_verify(args: &[String]) -> Result<()> {
+    let args = parse_verify_args(args)?;
+    let mut store = Store::load(&args.answers)?;
+
+    let (mut passed, mut failed, mut unknown, mut recorded) = (0, 0, 0, 0);
+
+    for &day in &args.days {
+        let runner = get_runner(day)?;
+
+        let fnames = if args.inputs.is_empty() {
+            vec![default_input(day)]
+        } else {
+            args.inputs.clone()
+        };
+
+        for fname in &fnames {
+            let name = if fnames.len() > 1 {
+                format!("day {:02} [{}]", day, display_name(fname))
+            } else {
+                format!("day {:02}", day)
+            };
+
+            let input = match read_input(fname) {
+                Ok(input) => input,
+                Err(e) => {
+                    println!("{}: error: {}", name, e);
+                    failed += 1;
+                    continue;
+                },
+            };
+            let hash = answers::input_hash(&input);
+
+            let run = match runner.run(&input, &Part::ALL).map_err(|e| in_file(e, fname)) {
+                Ok(run) => run,
+                Err(e) => {
+                    println!("{}: FAIL: {}", name, e);
+                    failed += 1;
+                    continue;
+                },
+            };
+
+            for result in &run.parts {
+                let answer = match &result.answer {
+                    Ok(Answer::Unsolved) => {
+                        println!("{} part {}: unsolved", name, result.part);
+                        continue;
+                    },
+                    Ok(answer) => answer,
+                    Err(e) => {
+                        println!("{} part {}: FAIL: {}", name, result.part, e);
+                        failed += 1;
+                        continue;
+                    },
+                };
+
+                match store.check(day, result.part, &hash, answer) {
+                    Verdict::Pass => {
+                        println!("{} part {}: pass", name, result.part);
+                        passed += 1;
+                    },
+                    Verdict::Fail(expected) => {
+                        println!("{} part {}: FAIL: got {}, expected {}", name, result.part, answer, expected);
+                        failed += 1;
+                    },
+                    Verdict::Unknown if args.record => {
+                        println!("{} part {}: recorded {}", name, result.part, answer);
+                        store.insert(day, result.part, &hash, answer);
+                        recorded += 1;
+                    },
+                    Verdict::Unknown => {
+                        println!("{} part {}: unknown ({})", name, result.part, answer);
+                        unknown += 1;
+                    },
+                }
+            }
+        }
+    }
+
+    if recorded > 0 {
+        store.save(&args.answers)?;
+    }
+
+    println!("{} passed, {} failed, {} unknown, {} recorded", passed, failed, unknown, recorded);
+
+    if failed > 0 {
+        return Err(format!("{} failure(s)", failed).into());
+    }
+
+    return Ok(());
+}
+
+fn cmd_fetch(args: &[String]) -> Result<()> {
+    let days = match args {
+        [arg] => parse_days(arg)?,
+        _ => return Err(USAGE.into()),
+    };
+    let mut client = Client::from_env()?;
+
+    for day in days {
+        let cached = client.is_cached(day);
+        let input = client.fetch_input(day)?;
+        println!("day {:02}: {} ({} bytes{})", day, client.input_path(day).display(),
+            input.len(), if cached { ", cached" } else { "" });
+    }
+
+    return Ok(());
+}
+
+struct SubmitArgs {
+    day: u32,
+    part: Part,
+    // Worked out from the input if not given
+    answer: Option<String>,
+    answers: String,
+}
+
+fn parse_submit_args(args: &[String]) -> Result<SubmitArgs> {
+    let mut positional = Vec::new();
+    let mut answers = answers::DEFAULT_FILE.to_string();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--answers" | "-a" => {
+                answers = iter.next().ok_or("--answers needs a value")?.to_string();
+            },
+            _ => positional.push(arg),
+        }
+    }
+
+    let (day, part, answer) = match positional[..] {
+        [day, part] => (day, part, None),
+        [day, part, answer] => (day, part, Some(answer.to_string())),
+        _ => return Err(USAGE.into()),
+    };
+    let day = day.parse::<u32>().map_err(|_| format!("invalid day '{}'", day))?;
+    let part = part.parse::<Part>()?;
+
+    return Ok(SubmitArgs{ day, part, answer, answers });
+}
+
+fn cmd_submit(args: &[String]) -> Result<()> {
+    let args = parse_submit_args(args)?;
+    let runner = get_runner(args.day)?;
+    let mut client = Client::from_env()?;
+
+    let input = client.fetch_input(args.day)?;
+    let answer = match args.answer {
+        Some(answer) => answer,
+        None => {
+            let run = runner.run(&input, &[args.part])?;
+            match &run.parts[0].answer {
+                Ok(Answer::Unsolved) => return Err(format!("day {} part {} isn't solved", args.day, args.part).into()),
+                Ok(answer) => answer.to_string(),
+                Err(e) => return Err(format!("part {}: {}", args.part, e).into()),
+            }
+        },
+    };
+
+    println!("day {:02} part {}: submitting {}", args.day, args.part, answer);
+
+    match client.submit(args.day, args.part, &answer)? {
+        Outcome::Correct => {
+            println!("correct");
+
+            // Remember it for `aoc verify`
+            let mut store = Store::load(&args.answers)?;
+            store.insert(args.day, args.part, &answers::input_hash(&input), &Answer::Text(answer));
+            store.save(&args.answers)?;
+        },
+        Outcome::Wrong(hint) => {
+            let hint = match hint {
+                Some(Hint::TooHigh) => ", too high",
+                Some(Hint::TooLow) => ", too low",
+                None => "",
+            };
+            return Err(format!("wrong answer{}", hint).into());
+        },
+        Outcome::TooSoon(wait) => {
+            return Err(format!("answered too recently, wait {}s", wait.as_secs()).into());
+        },
+        Outcome::WrongLevel => {
+            return Err("wrong level, is it already solved?".into());
+        },
+        Outcome::Unknown(text) => {
+            return Err(format!("unexpected response: {}", text).into());
+        },
+    }
+
+    return Ok(());
+}
+
+fn dispatch(args: &[String]) -> Result<()> {
+    match args.first().map(|s| s.as_str()) {
+        Some("run") => cmd_run(&args[1..]),
+        Some("bench") => cmd_bench(&args[1..]),
+        Some("verify") => cmd_verify(&args[1..]),
+        Some("fetch") => cmd_fetch(&args[1..]),
+        Some("submit") => cmd_submit(&args[1..]),
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+        _ => Err(USAGE.into()),
+    }
+}
+
+// Entry point for the `aoc` binary. `args` doesn't include the program name.
+pub fn main(args: &[String]) -> Result<()> {
+    return report(dispatch(args));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        return s.split_whitespace().map(|v| v.to_string()).collect();
+    }
+
+    #[test]
+    fn run_single_day() {
+        let a = parse_run_args(&args("17 --part 2 --input foo.txt")).unwrap();
+        assert_eq!(a.days, vec![17]);
+        assert_eq!(a.parts, vec![Part::Two]);
+        assert_eq!(a.inputs, vec!["foo.txt"]);
+    }
+
+    #[test]
+    fn run_several_inputs() {
+        let a = parse_run_args(&args("5 -i a.txt --input - -i b.txt")).unwrap();
+        assert_eq!(a.days, vec![5]);
+        assert_eq!(a.inputs, vec!["a.txt", STDIN, "b.txt"]);
+    }
+
+    #[test]
+    fn run_all_days() {
+        let a = parse_run_args(&args("all")).unwrap();
+        assert_eq!(a.days.len(), days::ALL.len());
+        assert_eq!(a.parts, Part::ALL.to_vec());
+        assert!(a.inputs.is_empty());
+    }
+
+    #[test]
+    fn run_json() {
+        let a = parse_run_args(&args("all --format json")).unwrap();
+        assert_eq!(a.format, Format::Json);
+        assert_eq!(parse_run_args(&args("3")).unwrap().format, Format::Text);
+    }
+
+    #[test]
+    fn run_dot() {
+        let a = parse_run_args(&args("25 --dot 25.dot")).unwrap();
+        assert_eq!(a.drawing.dot.as_deref(), Some("25.dot"));
+        assert_eq!(parse_run_args(&args("25")).unwrap().drawing, Drawing::default());
+
+        assert!(parse_run_args(&args("25 --dot")).is_err());
+        assert!(parse_run_args(&args("all --dot all.dot")).is_err());
+        assert!(parse_run_args(&args("25 -i a.txt -i b.txt --dot 25.dot")).is_err());
+    }
+
+    #[test]
+    fn run_draw() {
+        let a = parse_run_args(&args("17 --draw 17.ppm --frames -")).unwrap();
+        assert_eq!(a.drawing.draw.as_deref(), Some("17.ppm"));
+        assert_eq!(a.drawing.frames.as_deref(), Some("-"));
+        assert_eq!(a.drawing.dot, None);
+
+        assert!(parse_run_args(&args("17 --frames")).is_err());
+        let e = parse_run_args(&args("all --frames frames")).err().unwrap();
+        assert_eq!(e.to_string(), "--frames can only be used with a single day and input");
+    }
+
+    #[test]
+    fn json_records() {
+        let result = PartResult{
+            part: Part::Two,
+            answer: Ok(Answer::Int(5905)),
+            elapsed: std::time::Duration::from_nanos(1234),
+        };
+        let answer = Answer::Int(5905);
+
+        assert_eq!(
+            json_record(7, &result, &answer, None),
+            "{\"day\":7,\"part\":2,\"answer\":5905,\"elapsed_ns\":1234}"
+        );
+        assert_eq!(
+            json_record(7, &result, &answer, Some(STDIN)),
+            "{\"day\":7,\"part\":2,\"answer\":5905,\"elapsed_ns\":1234,\"input\":\"<stdin>\"}"
+        );
+    }
+
+    #[test]
+    fn run_bad_args() {
+        assert!(parse_run_args(&args("")).is_err());
+        assert!(parse_run_args(&args("seventeen")).is_err());
+        assert!(parse_run_args(&args("17 --part 3")).is_err());
+        assert!(parse_run_args(&args("17 --part")).is_err());
+        assert!(parse_run_args(&args("17 18")).is_err());
+        assert!(parse_run_args(&args("17 --format yaml")).is_err());
+        assert!(parse_run_args(&args("all --input foo.txt")).is_err());
+    }
+
+    #[test]
+    fn bench_args() {
+        let a = parse_bench_args(&args("12 -n 50 --save new.txt --baseline old.txt")).unwrap();
+        assert_eq!(a.days, vec![12]);
+        assert_eq!(a.iters, 50);
+        assert_eq!(a.save.as_deref(), Some("new.txt"));
+        assert_eq!(a.baseline.as_deref(), Some("old.txt"));
+
+        let a = parse_bench_args(&args("all")).unwrap();
+        assert_eq!(a.iters, 10);
+        assert_eq!(a.input, None);
+
+        assert!(parse_bench_args(&args("12 --iters 0")).is_err());
+        assert!(parse_bench_args(&args("all -i foo.txt")).is_err());
+    }
+
+    #[test]
+    fn verify_args() {
+        let a = parse_verify_args(&args("all")).unwrap();
+        assert_eq!(a.answers, answers::DEFAULT_FILE);
+        assert!(!a.record);
+
+        let a = parse_verify_args(&args("19 -i a.txt -i b.txt --answers mine.txt --record")).unwrap();
+        assert_eq!(a.days, vec![19]);
+        assert_eq!(a.inputs, vec!["a.txt", "b.txt"]);
+        assert_eq!(a.answers, "mine.txt");
+        assert!(a.record);
+
+        assert!(parse_verify_args(&args("all -i a.txt")).is_err());
+    }
+
+    #[test]
+    fn submit_args() {
+        let a = parse_submit_args(&args("7 2")).unwrap();
+        assert_eq!((a.day, a.part, a.answer), (7, Part::Two, None));
+        assert_eq!(a.answers, answers::DEFAULT_FILE);
+
+        let a = parse_submit_args(&args("7 1 6440 --answers mine.txt")).unwrap();
+        assert_eq!((a.day, a.part, a.answer), (7, Part::One, Some("6440".to_string())));
+        assert_eq!(a.answers, "mine.txt");
+
+        assert!(parse_submit_args(&args("7")).is_err());
+        assert!(parse_submit_args(&args("7 3")).is_err());
+        assert!(parse_submit_args(&args("x 1")).is_err());
+    }
+
+    #[test]
+    fn default_input_path() {
+        assert_eq!(default_input(8), "08/input.txt");
+        assert_eq!(default_input(25), "25/input.txt");
+    }
+}
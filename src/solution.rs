@@ -0,0 +1,274 @@
+//! The interface every day implements, so that they can all be driven by the
+//! same runner.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::dot::Dot;
+use crate::json;
+use crate::picture::Picture;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part '{}', expected 1 or 2", s)),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+    // For the parts I haven't solved (yet)
+    Unsolved,
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(v: $t) -> Self {
+                    Answer::Int(v as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, i64, u8, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(v: String) -> Self {
+        Answer::Text(v)
+    }
+}
+
+impl Answer {
+    // Numbers as numbers, text as a string and unsolved as null
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Int(v) => v.to_string(),
+            Answer::Text(s) => json::string(s),
+            Answer::Unsolved => "null".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{}", v),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+pub trait Solution {
+    const DAY: u32;
+
+    // Whatever the puzzle input parses into, shared by both parts
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Answer>;
+    fn part2(input: &Self::Input) -> Result<Answer>;
+
+    // The input drawn as a graph, for the days where looking at it helps
+    fn dot(_input: &Self::Input) -> Option<Dot> {
+        return None;
+    }
+
+    // Pictures of the solver at work, as the frames of an animation (or just
+    // one), for the grid puzzles. None of them means there's nothing to draw.
+    fn draw(_input: &Self::Input) -> Result<Vec<Picture>> {
+        return Ok(Vec::new());
+    }
+
+    // Parses the input and solves one part of it, mostly for the tests
+    fn solve(input: &str, part: Part) -> Result<Answer> {
+        let parsed = Self::parse(input)?;
+
+        match part {
+            Part::One => Self::part1(&parsed),
+            Part::Two => Self::part2(&parsed),
+        }
+    }
+}
+
+pub struct PartResult {
+    pub part: Part,
+    pub answer: Result<Answer>,
+    pub elapsed: Duration,
+}
+
+pub struct Run {
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+// Object-safe view of a Solution, so that all the days can go in one list.
+pub trait Runner {
+    fn day(&self) -> u32;
+
+    // Parses the input once, then runs each of the requested parts on it.
+    fn run(&self, input: &str, parts: &[Part]) -> Result<Run>;
+
+    // Parses the input and draws it, if the day knows how
+    fn dot(&self, input: &str) -> Result<Option<Dot>>;
+
+    // Parses the input and draws the solver working on it
+    fn draw(&self, input: &str) -> Result<Vec<Picture>>;
+}
+
+impl<S: Solution> Runner for S {
+    fn day(&self) -> u32 {
+        return S::DAY;
+    }
+
+    fn run(&self, input: &str, parts: &[Part]) -> Result<Run> {
+        let start = Instant::now();
+        let parsed = S::parse(input)?;
+        let parse_elapsed = start.elapsed();
+
+        let mut results = Vec::new();
+        for &part in parts {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => S::part1(&parsed),
+                Part::Two => S::part2(&parsed),
+            };
+
+            results.push(PartResult{
+                part,
+                answer,
+                elapsed: start.elapsed(),
+            });
+        }
+
+        return Ok(Run{
+            parse_elapsed,
+            parts: results,
+        });
+    }
+
+    fn dot(&self, input: &str) -> Result<Option<Dot>> {
+        let parsed = S::parse(input)?;
+        return Ok(S::dot(&parsed));
+    }
+
+    fn draw(&self, input: &str) -> Result<Vec<Picture>> {
+        let parsed = S::parse(input)?;
+        return S::draw(&parsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u32 = 99;
+        type Input = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            let mut vals = Vec::new();
+            for line in input.lines() {
+                vals.push(line.parse::<i64>()?);
+            }
+            return Ok(vals);
+        }
+
+        fn part1(input: &Self::Input) -> Result<Answer> {
+            return Ok(input.iter().sum::<i64>().into());
+        }
+
+        fn part2(_input: &Self::Input) -> Result<Answer> {
+            return Ok(Answer::Unsolved);
+        }
+    }
+
+    #[test]
+    fn parse_part() {
+        assert_eq!("1".parse::<Part>(), Ok(Part::One));
+        assert_eq!("2".parse::<Part>(), Ok(Part::Two));
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn answer_display() {
+        assert_eq!(Answer::from(42u64).to_string(), "42");
+        assert_eq!(Answer::from(-3i32).to_string(), "-3");
+        assert_eq!(Answer::from("abc".to_string()).to_string(), "abc");
+        assert_eq!(Answer::Unsolved.to_string(), "unsolved");
+    }
+
+    #[test]
+    fn answer_json() {
+        assert_eq!(Answer::from(42u64).to_json(), "42");
+        assert_eq!(Answer::from("a\"b".to_string()).to_json(), "\"a\\\"b\"");
+        assert_eq!(Answer::Unsolved.to_json(), "null");
+    }
+
+    #[test]
+    fn run_through_runner() {
+        let runner: &dyn Runner = &Sum;
+        assert_eq!(runner.day(), 99);
+
+        let run = runner.run("1\n2\n3\n", &[Part::Two, Part::One]).unwrap();
+        assert_eq!(run.parts.len(), 2);
+        assert_eq!(run.parts[0].part, Part::Two);
+        assert_eq!(run.parts[0].answer.as_ref().unwrap(), &Answer::Unsolved);
+        assert_eq!(run.parts[1].answer.as_ref().unwrap(), &Answer::Int(6));
+
+        // Nothing to draw
+        assert!(runner.dot("1\n").unwrap().is_none());
+        assert!(runner.dot("x\n").is_err());
+        assert!(runner.draw("1\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn solve_one_part() {
+        assert_eq!(Sum::solve("4\n5\n", Part::One).unwrap(), Answer::Int(9));
+        assert!(Sum::solve("x\n", Part::One).is_err());
+    }
+
+    #[test]
+    fn parse_error_stops_run() {
+        assert!(Sum.run("1\nx\n", &Part::ALL).is_err());
+    }
+}
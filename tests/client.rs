@@ -0,0 +1,120 @@
+// The fetch/submit client against the local stand-in for the site.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use aoc::client::{Client, Hint, Outcome};
+use aoc::mockserver::MockServer;
+use aoc::solution::Part;
+
+const SESSION: &str = "53616c7465645f5f";
+
+// A fresh, empty cache directory for each test
+fn cache_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    return dir;
+}
+
+fn client(server: &MockServer, dir: &PathBuf) -> Client {
+    let mut client = Client::new(&server.url, SESSION, dir.clone());
+    client.min_interval = Duration::ZERO;
+    return client;
+}
+
+#[test]
+fn fetch_and_cache() {
+    let server = MockServer::start(SESSION).unwrap();
+    server.add_day(7, "32T3K 765\n", "6440", "5905");
+    let dir = cache_dir("fetch");
+
+    let mut client = client(&server, &dir);
+    assert!(!client.is_cached(7));
+    assert_eq!(client.fetch_input(7).unwrap(), "32T3K 765\n");
+    assert!(client.is_cached(7));
+    assert_eq!(fs::read_to_string(dir.join("07/input.txt")).unwrap(), "32T3K 765\n");
+
+    // Second time comes from the cache
+    assert_eq!(client.fetch_input(7).unwrap(), "32T3K 765\n");
+    assert_eq!(server.requests(), vec!["GET /2023/day/7/input"]);
+
+    // Days the server doesn't have aren't cached
+    assert!(client.fetch_input(8).is_err());
+    assert!(!client.is_cached(8));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn bad_session() {
+    let server = MockServer::start(SESSION).unwrap();
+    server.add_day(1, "1abc2\n", "12", "12");
+    let dir = cache_dir("session");
+
+    let mut client = Client::new(&server.url, "nope", dir.clone());
+    let e = client.fetch_input(1).unwrap_err();
+    assert!(e.to_string().contains("Please log in"), "{}", e);
+    assert!(!client.is_cached(1));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn submit_answers() {
+    let server = MockServer::start(SESSION).unwrap();
+    server.add_day(7, "32T3K 765\n", "6440", "5905");
+    let dir = cache_dir("submit");
+
+    let mut client = client(&server, &dir);
+
+    // Part 2 can't be answered before part 1
+    assert_eq!(client.submit(7, Part::Two, "5905").unwrap(), Outcome::WrongLevel);
+
+    assert_eq!(client.submit(7, Part::One, "6440").unwrap(), Outcome::Correct);
+    assert_eq!(client.submit(7, Part::One, "6440").unwrap(), Outcome::WrongLevel);
+
+    server.set_wait(Duration::from_secs(90));
+    assert_eq!(client.submit(7, Part::Two, "6000").unwrap(), Outcome::Wrong(Some(Hint::TooHigh)));
+    assert_eq!(client.submit(7, Part::Two, "5905").unwrap(), Outcome::TooSoon(Duration::from_secs(90)));
+
+    // Nothing that isn't an answer gets sent
+    assert!(client.submit(7, Part::Two, "1&level=1").is_err());
+    assert_eq!(server.requests().len(), 5);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn wrong_answer_lockout() {
+    let server = MockServer::start(SESSION).unwrap();
+    server.add_day(2, "", "8", "2286");
+    server.set_wait(Duration::ZERO);
+    let dir = cache_dir("lockout");
+
+    let mut client = client(&server, &dir);
+    assert_eq!(client.submit(2, Part::One, "7").unwrap(), Outcome::Wrong(Some(Hint::TooLow)));
+    assert_eq!(client.submit(2, Part::One, "eight").unwrap(), Outcome::Wrong(None));
+    assert_eq!(client.submit(2, Part::One, "8").unwrap(), Outcome::Correct);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn rate_limit() {
+    let server = MockServer::start(SESSION).unwrap();
+    server.add_day(1, "1abc2\n", "12", "12");
+    server.add_day(2, "", "8", "2286");
+    let dir = cache_dir("rate");
+
+    let mut client = client(&server, &dir);
+    client.min_interval = Duration::from_millis(200);
+
+    let start = Instant::now();
+    client.fetch_input(1).unwrap();
+    client.fetch_input(2).unwrap();
+    client.submit(1, Part::One, "12").unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(400));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,136 @@
+// Day 01's scanner, on the awkward lines which aren't in the examples.
+
+use std::io::BufReader;
+
+use aoc::days::day01::{Day01, Fold, Scanner, Vocabulary};
+use aoc::solution::{Answer, Part, Solution};
+
+fn part2(document: &str) -> Answer {
+    return Day01::solve(document, Part::Two).unwrap();
+}
+
+#[test]
+fn overlapping_words() {
+    // Both words count, from whichever end they're looked for
+    assert_eq!(part2("eightwo\n"), Answer::Int(82));
+    assert_eq!(part2("oneight\n"), Answer::Int(18));
+    assert_eq!(part2("twone\nsevenine\n"), Answer::Int(21 + 79));
+    assert_eq!(part2("xtwone3four"), Answer::Int(24));
+
+    // One token is both the first and the last
+    assert_eq!(part2("zzfivezz\n"), Answer::Int(55));
+}
+
+#[test]
+fn no_digits() {
+    assert_eq!(part2("nothing\n\n"), Answer::Int(0));
+    assert_eq!(Day01::solve("one\n", Part::One).unwrap(), Answer::Int(0));
+}
+
+#[test]
+fn streaming() {
+    // Lines split across reads, CRLF line endings and no newline at the end
+    let document = "1abc2\r\npqr3stu8vwx\n\nnothing\ntreb7uchet";
+    let reader = BufReader::with_capacity(4, document.as_bytes());
+
+    assert_eq!(Scanner::digits().calibration_sum(reader).unwrap(), 12 + 38 + 77);
+}
+
+#[test]
+fn raw_bytes() {
+    // Not UTF-8, but the digits are still found
+    let document: &[u8] = b"\xff1\xfe\n\xc0two\xc1\n";
+    let scanner = Scanner::new(&Vocabulary::english());
+    assert_eq!(scanner.calibration_sum(document).unwrap(), 11 + 22);
+}
+
+const VOCABULARY: &str = "\
+# Numbers up to twelve
+[en fold=ascii]
+one = 1
+two = 2
+ten = 10
+twelve = 12
+
+[de fold=unicode]
+eins = 1
+zwei = 2
+drei = 3
+zwölf = 12
+
+[fr fold=unicode]
+un = 1
+deux = 2
+";
+
+fn sum(vocabulary: &Vocabulary, document: &str) -> u64 {
+    return Scanner::new(vocabulary).calibration_sum(document.as_bytes()).unwrap();
+}
+
+#[test]
+fn vocabulary_locales() {
+    let en = Vocabulary::parse(VOCABULARY, &["en"]).unwrap();
+    assert_eq!(en.fold, Fold::Ascii);
+    assert_eq!(en.words.len(), 4);
+
+    // Case doesn't matter, and "twelve" is a 1 at the start and a 2 at the end
+    assert_eq!(sum(&en, "ONEx\nTwelvex\nxten\nxtwelve3\n"), 11 + 12 + 10 + 13);
+
+    // Several at once, and upper case outside ASCII
+    let both = Vocabulary::parse(VOCABULARY, &["de", "fr"]).unwrap();
+    assert_eq!(both.words.len(), 6);
+    assert_eq!(sum(&both, "ZWÖLFundDEUX\nundrei\n"), 12 + 13);
+}
+
+#[test]
+fn vocabulary_offsets() {
+    // "K" (the Kelvin sign) lowers to "k", which is two bytes shorter, and
+    // "Ⱥ" to "ⱥ", which is a byte longer, so the matches have to be moved
+    // back into the line
+    let vocabulary = Vocabulary::parse("[x fold=unicode]\nstraße = 1\nⱥk = 2\n", &[]).unwrap();
+    let scanner = Scanner::new(&vocabulary);
+
+    let line = "ak STRAẞE";
+    let (first, last) = scanner.first_and_last(line.as_bytes()).unwrap();
+    assert_eq!((first.value, last.value), (1, 1));
+    assert_eq!(&line[first.start..first.end], "STRAẞE");
+
+    let line = "aȺKb";
+    let (first, _) = scanner.first_and_last(line.as_bytes()).unwrap();
+    assert_eq!(first.value, 2);
+    assert_eq!(&line[first.start..first.end], "ȺK");
+}
+
+#[test]
+fn vocabulary_errors() {
+    let error = |text: &str, locales: &[&str]| Vocabulary::parse(text, locales).unwrap_err().to_string();
+
+    assert!(error(VOCABULARY, &[]).contains("en, de, fr"));
+    assert!(error(VOCABULARY, &["es"]).contains("no locale 'es'"));
+    assert!(error(VOCABULARY, &["en", "de"]).contains("fold differently"));
+    assert!(error("[a]\none = 1\n[a]\n", &[]).contains("already defined"));
+    assert!(Vocabulary::parse("[a]\none = 1\nONE = 1\n", &[]).is_ok());
+    assert!(error("[a fold=ascii]\none = 1\nONE = 1\n", &[]).contains("twice"));
+    assert!(error("one = 1\n", &[]).contains("before any words"));
+    assert!(error("[a]\none = x\n", &[]).starts_with("2:7:"));
+    assert!(error("[a fold=maybe]\n", &[]).contains("invalid fold"));
+}
+
+#[test]
+fn explain() {
+    let scanner = Scanner::new(&Vocabulary::english());
+    let mut out = Vec::new();
+    let sum = scanner.explain("xtwone3four\r\noneight\nnothing\n7\n".as_bytes(), &mut out).unwrap();
+    assert_eq!(sum, 24 + 18 + 77);
+
+    let out = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines, vec![
+        "1: x[two]ne3(four)  first \"two\" at 1..4, last \"four\" at 7..11 = 24",
+        "2: [on(e]ight)  first \"one\" at 0..3, last \"eight\" at 2..7 = 18",
+        "3: nothing  no digits = 0",
+        "4: [7]  first \"7\" at 0..1, last \"7\" at 0..1 = 77",
+        "sum 119 from 4 line(s)",
+        "1 line(s) without any digits: 3",
+    ]);
+}
@@ -0,0 +1,142 @@
+// Day 02's games against bags other than the one in the puzzle.
+
+use aoc::days::day02::{self, Cubes, Day02, Game, Shortfall};
+use aoc::days::day02::inference::{self, Model};
+use aoc::solution::Solution;
+
+fn games() -> Vec<Game> {
+    let fname = format!("{}/tests/samples/02.txt", env!("CARGO_MANIFEST_DIR"));
+    return Day02::parse(&std::fs::read_to_string(fname).unwrap()).unwrap();
+}
+
+fn cubes(s: &str) -> Cubes {
+    return s.parse().unwrap();
+}
+
+#[test]
+fn several_bags() {
+    let games = games();
+    assert_eq!(day02::possible_ids(&games, &day02::puzzle_bag()), vec![1, 2, 5]);
+    assert_eq!(day02::possible_ids(&games, &cubes("20 red, 13 green, 15 blue")), vec![1, 2, 3, 4, 5]);
+    assert_eq!(day02::possible_ids(&games, &cubes("1 red")), Vec::<u32>::new());
+
+    // Other colours are fine as long as the bag has them
+    let games = Day02::parse("Game 7: 2 purple, 1 red; 3 purple\n").unwrap();
+    assert_eq!(day02::possible_ids(&games, &day02::puzzle_bag()), Vec::<u32>::new());
+    assert_eq!(day02::possible_ids(&games, &cubes("3 purple, 1 red")), vec![7]);
+    assert_eq!(day02::possible_ids(&games, &cubes("3 purple, 1 red, 0 teal")), vec![7]);
+}
+
+#[test]
+fn min_bag() {
+    let games = games();
+    assert_eq!(games[0].min_bag(), cubes("4 red, 2 green, 6 blue"));
+    assert_eq!(games[2].min_bag().power(), Some(1560));
+    assert_eq!(games[2].min_bag().total(), 39);
+
+    // Which is always possible, and nothing smaller is
+    for game in &games {
+        let bag = game.min_bag();
+        assert!(game.possible_with(&bag));
+        for (colour, n) in bag.iter() {
+            let fewer: Vec<String> = bag.iter()
+                .map(|(c, m)| format!("{} {}", if c == colour { n - 1 } else { m }, c))
+                .collect();
+            let smaller = cubes(&fewer.join(", "));
+            assert!(!game.possible_with(&smaller), "{} for game {}", smaller, game.id);
+        }
+    }
+}
+
+#[test]
+fn too_many_cubes() {
+    // Bigger than a u32, but still fits
+    let big = cubes("100000 red, 100000 green");
+    assert_eq!(big.power(), Some(10_000_000_000));
+
+    let games = Day02::parse("Game 1: 4000000000 red, 4000000000 green, 4000000000 blue\n").unwrap();
+    assert_eq!(games[0].min_bag().power(), None);
+    assert_eq!(games[0].min_bag().total(), 12_000_000_000);
+    let e = Day02::part2(&games).unwrap_err();
+    assert_eq!(e.to_string(), "the power of game 1 is too big");
+}
+
+#[test]
+fn tightest_hand() {
+    let games = games();
+    let bag = day02::puzzle_bag();
+
+    assert_eq!(games[0].tightest_hand(&bag), None);
+    assert_eq!(games[2].tightest_hand(&bag), Some(Shortfall{
+        hand: 0, colour: "red".to_string(), seen: 20, in_bag: 12,
+    }));
+
+    // Whichever hand is furthest over, even if it isn't the first one over
+    let bag = cubes("10 red, 1 green, 14 blue");
+    assert_eq!(games[2].tightest_hand(&bag), Some(Shortfall{
+        hand: 1, colour: "green".to_string(), seen: 13, in_bag: 1,
+    }));
+}
+
+#[test]
+fn bags_file() {
+    let bags = day02::parse_bags("# Some bags\n12 red, 13 green, 14 blue\n\n1 purple\n").unwrap();
+    assert_eq!(bags, vec![day02::puzzle_bag(), cubes("1 purple")]);
+    assert_eq!(bags[1].to_string(), "1 purple");
+
+    let e = day02::parse_bags("1 red\n2 red, 3 red\n").unwrap_err();
+    assert_eq!((e.line, e.col, e.msg.as_str()), (2, 10, "colour is already in the hand"));
+}
+
+#[test]
+fn probabilities_add_up() {
+    // Over every hand of 3 that could come out, either way
+    let bag = cubes("2 red, 3 green, 1 blue");
+    for model in [Model::WithReplacement, Model::WithoutReplacement] {
+        let mut total = 0.0;
+        for r in 0..=3 {
+            for g in 0..=3 - r {
+                let game = &Day02::parse(&format!("Game 1: {} red, {} green, {} blue\n", r, g, 3 - r - g)).unwrap()[0];
+                total += inference::probability(game, &bag, model);
+            }
+        }
+        assert!((total - 1.0).abs() < 1e-9, "{:?} adds up to {}", model, total);
+    }
+
+    // Two hands are independent
+    let game = &Day02::parse("Game 1: 1 red; 1 blue\n").unwrap()[0];
+    let p = inference::probability(game, &bag, Model::WithoutReplacement);
+    assert!((p - (2.0 / 6.0) * (1.0 / 6.0)).abs() < 1e-12);
+
+    // Too many reds at once can only happen one at a time
+    let game = &Day02::parse("Game 1: 3 red\n").unwrap()[0];
+    assert_eq!(inference::probability(game, &bag, Model::WithoutReplacement), 0.0);
+    assert!((inference::probability(game, &bag, Model::WithReplacement) - 1.0 / 27.0).abs() < 1e-12);
+
+    // Or a hand bigger than the whole bag
+    let game = &Day02::parse("Game 1: 3 red; 1 blue\n").unwrap()[0];
+    let small = cubes("1 red, 1 blue");
+    assert_eq!(inference::ln_likelihood(game, &small, Model::WithoutReplacement), f64::NEG_INFINITY);
+    assert_eq!(inference::probability(game, &small, Model::WithoutReplacement), 0.0);
+}
+
+#[test]
+fn most_likely_bag() {
+    let games = games();
+    let game2 = std::slice::from_ref(&games[1]);
+
+    // One at a time it's just the proportions of each colour seen
+    assert_eq!(inference::mle(game2, 13, Model::WithReplacement), Some(cubes("6 blue, 6 green, 1 red")));
+    let (bag, _) = inference::mle_any_size(game2, 50, Model::WithReplacement).unwrap();
+    assert_eq!(bag, cubes("6 blue, 6 green, 1 red"));
+
+    // All at once there have to be enough for every hand
+    assert_eq!(inference::mle(game2, 7, Model::WithoutReplacement), None);
+    assert_eq!(inference::mle(game2, 8, Model::WithoutReplacement), Some(games[1].min_bag()));
+    assert_eq!(inference::mle(game2, 2, Model::WithReplacement), None);
+
+    // Every game from the one bag
+    let bag = inference::mle(&games, 60, Model::WithoutReplacement).unwrap();
+    assert_eq!(bag.total(), 60);
+    assert!(games.iter().all(|g| g.possible_with(&bag)));
+}
@@ -0,0 +1,509 @@
+// The quick ways of solving some of the days, checked against slow but
+// obviously right ones on lots of small random inputs. Any failures are
+// shrunk down to the smallest input which still gets a different answer.
+
+use std::collections::BTreeSet;
+
+use aoc::days::*;
+use aoc::prop::{self, Rng, Shrink};
+use aoc::solution::{Answer, Part, Solution};
+
+// The answer to compare, or None if it couldn't get one
+fn answer<S: Solution>(input: &str, part: Part) -> Option<Answer> {
+    return S::solve(input, part).ok();
+}
+
+// Adds the input to the error, so the failure can be tried by hand
+fn with_input(result: Result<(), String>, input: &str) -> Result<(), String> {
+    return result.map_err(|e| format!("{}\ninput:\n{}", e, input));
+}
+
+// Day 06: counting the winning hold times from the roots of a quadratic,
+// against trying every one of them
+
+type Races = Vec<(u32, u64)>;
+
+fn races_input(races: &Races) -> String {
+    let times: Vec<String> = races.iter().map(|r| r.0.to_string()).collect();
+    let distances: Vec<String> = races.iter().map(|r| r.1.to_string()).collect();
+    return format!("Time: {}\nDistance: {}\n", times.join(" "), distances.join(" "));
+}
+
+fn count_wins(time: u64, distance: u64) -> u64 {
+    return (0..=time).filter(|hold| hold * (time - hold) > distance).count() as u64;
+}
+
+fn gen_races(rng: &mut Rng) -> Races {
+    let n = rng.between(1, 4);
+    return (0..n).map(|_| {
+        let time = rng.below(60);
+        // Around the best distance, so there are only a few ways to win
+        let best = time * time / 4;
+        return (time as u32, rng.below(best + 4));
+    }).collect();
+}
+
+#[test]
+fn day06() {
+    prop::check("day06", 2000, gen_races, |races| {
+        let input = races_input(races);
+        if races.is_empty() {
+            return with_input(prop::same(answer::<day06::Day06>(&input, Part::One), None), &input);
+        }
+
+        let product = races.iter().map(|&(t, d)| count_wins(t as u64, d)).product::<u64>();
+        with_input(prop::same(answer::<day06::Day06>(&input, Part::One), Some(product.into())), &input)?;
+
+        // The digits all run together in part 2, which takes too long to
+        // brute force if there are many of them (see day06_long for those)
+        let time: String = races.iter().map(|r| r.0.to_string()).collect();
+        let distance: String = races.iter().map(|r| r.1.to_string()).collect();
+        if time.len() <= 4 {
+            let wins = count_wins(time.parse().unwrap(), distance.parse().unwrap());
+            with_input(prop::same(answer::<day06::Day06>(&input, Part::Two), Some(wins.into())), &input)?;
+        }
+
+        return Ok(());
+    });
+}
+
+// Part 2 again, but with times too long to try every hold for. The winning
+// holds are all together around the middle, so with a distance close to the
+// best there are few enough of them to find one at a time from there.
+
+fn count_wins_from_middle(time: u64, distance: u64) -> u64 {
+    let wins = |hold: u64| (hold as u128) * ((time - hold) as u128) > distance as u128;
+
+    let mut count = 0;
+    let mut hold = time / 2;
+    while wins(hold) {
+        count += 1;
+        if hold == 0 {
+            break;
+        }
+        hold -= 1;
+    }
+    let mut hold = time / 2 + 1;
+    while hold <= time && wins(hold) {
+        count += 1;
+        hold += 1;
+    }
+
+    return count;
+}
+
+// Into two numbers (which can start with 0s), for the two races they came from
+fn halves(n: u64) -> (String, String) {
+    let s = n.to_string();
+    let (a, b) = s.split_at(s.len() / 2);
+    return (a.to_string(), b.to_string());
+}
+
+// A part 2 time which doesn't fit in a u32, and how far short of the best
+// distance the record is
+fn gen_long_race(rng: &mut Rng) -> (u64, u64) {
+    return (rng.between(u32::MAX as u64 + 1, 8_000_000_000), rng.below(1_000_000));
+}
+
+#[test]
+fn day06_long() {
+    prop::check("day06_long", 200, gen_long_race, |&(time, slack)| {
+        if time < 10 {
+            return Ok(());
+        }
+        let distance = (time / 2) * (time - time / 2) - slack.min((time / 2) * (time - time / 2));
+
+        let (t1, t2) = halves(time);
+        let (d1, d2) = halves(distance);
+        let input = format!("Time: {} {}\nDistance: {} {}\n", t1, t2, d1, d2);
+
+        // Which still works for part 1, with each half on its own
+        let (t1, t2): (u64, u64) = (t1.parse().unwrap(), t2.parse().unwrap());
+        let (d1, d2): (u64, u64) = (d1.parse().unwrap(), d2.parse().unwrap());
+        let product = count_wins_from_middle(t1, d1).checked_mul(count_wins_from_middle(t2, d2));
+        let product = product.map(|p| p.into());
+        with_input(prop::same(answer::<day06::Day06>(&input, Part::One), product), &input)?;
+
+        let wins = count_wins_from_middle(time, distance);
+        return with_input(prop::same(answer::<day06::Day06>(&input, Part::Two), Some(wins.into())), &input);
+    });
+}
+
+// Day 05: mapping whole ranges of seeds at once, against mapping each seed
+// one at a time
+
+#[derive(Debug, Clone)]
+struct Almanac {
+    // Start and length
+    seeds: Vec<(u64, u64)>,
+    // Each map's destination start, source start and length
+    maps: Vec<Vec<(u64, u64, u64)>>,
+}
+
+impl Shrink for Almanac {
+    fn shrink(&self) -> Vec<Self> {
+        let mut out: Vec<Almanac> = self.seeds.shrink().into_iter()
+            .map(|seeds| Almanac{ seeds, maps: self.maps.clone() })
+            .collect();
+        out.extend(self.maps.shrink().into_iter().map(|maps| Almanac{ seeds: self.seeds.clone(), maps }));
+        return out;
+    }
+}
+
+impl Almanac {
+    fn input(&self) -> String {
+        let seeds: Vec<String> = self.seeds.iter().map(|s| format!("{} {}", s.0, s.1)).collect();
+        let mut blocks = vec![format!("seeds: {}", seeds.join(" "))];
+
+        for (i, map) in self.maps.iter().enumerate() {
+            let mut block = format!("m{}-to-m{} map:", i, i + 1).replace("m0-", "seed-");
+            for range in map {
+                block += &format!("\n{} {} {}", range.0, range.1, range.2);
+            }
+            blocks.push(block);
+        }
+
+        return blocks.join("\n\n") + "\n";
+    }
+
+    // There's at least one map, and like in the puzzle their ranges don't
+    // overlap, which is assumed when they're parsed
+    fn valid(&self) -> bool {
+        return !self.maps.is_empty() && self.maps.iter().all(|map| {
+            let mut sources: Vec<(u64, u64)> = map.iter().map(|r| (r.1, r.1 + r.2)).collect();
+            sources.sort();
+            return sources.windows(2).all(|w| w[0].1 <= w[1].0);
+        });
+    }
+
+    fn location(&self, seed: u64) -> u64 {
+        let mut v = seed;
+        for map in &self.maps {
+            if let Some(range) = map.iter().find(|r| (r.1..r.1 + r.2).contains(&v)) {
+                v = range.0 + (v - range.1);
+            }
+        }
+
+        return v;
+    }
+}
+
+fn gen_almanac(rng: &mut Rng) -> Almanac {
+    let seeds = (0..rng.between(1, 3)).map(|_| (rng.below(100), rng.below(20))).collect();
+
+    let mut maps = Vec::new();
+    for _ in 0..rng.between(1, 4) {
+        // Ranges which don't overlap, one after the other with gaps
+        let mut map = Vec::new();
+        let mut start = rng.below(10);
+        for _ in 0..rng.below(4) {
+            let len = rng.between(1, 20);
+            map.push((rng.below(100), start, len));
+            start += len + rng.below(10);
+        }
+        maps.push(map);
+    }
+
+    return Almanac{ seeds, maps };
+}
+
+#[test]
+fn day05() {
+    prop::check("day05", 1000, gen_almanac, |almanac| {
+        if !almanac.valid() {
+            return Ok(());
+        }
+        let input = almanac.input();
+
+        let singles = almanac.seeds.iter().flat_map(|s| [s.0, s.1]);
+        let lowest = singles.map(|s| almanac.location(s)).min();
+        with_input(prop::same(answer::<day05::Day05>(&input, Part::One), lowest.map(Answer::from)), &input)?;
+
+        let ranges = almanac.seeds.iter().flat_map(|s| s.0..s.0 + s.1);
+        let lowest = ranges.map(|s| almanac.location(s)).min();
+        with_input(prop::same(answer::<day05::Day05>(&input, Part::Two), lowest.map(Answer::from)), &input)?;
+
+        return Ok(());
+    });
+}
+
+// Day 12: the memoised search through where each group could go, against
+// trying every way of filling in the unknowns
+
+#[derive(Debug, Clone)]
+struct Record {
+    pattern: String,
+    groups: Vec<usize>,
+}
+
+// Keeping them valid records, which have some springs and no empty groups
+impl Shrink for Record {
+    fn shrink(&self) -> Vec<Self> {
+        let mut out = Vec::new();
+        let record = |pattern: String, groups: Vec<usize>| Record{ pattern, groups };
+
+        for i in 0..self.groups.len() {
+            if self.groups.len() > 1 {
+                let mut groups = self.groups.clone();
+                groups.remove(i);
+                out.push(record(self.pattern.clone(), groups));
+            }
+            if self.groups[i] > 1 {
+                let mut groups = self.groups.clone();
+                groups[i] -= 1;
+                out.push(record(self.pattern.clone(), groups));
+            }
+        }
+
+        for (i, c) in self.pattern.char_indices() {
+            if self.pattern.len() > 1 {
+                let pattern = self.pattern[..i].to_string() + &self.pattern[i + 1..];
+                out.push(record(pattern, self.groups.clone()));
+            }
+            if c != '.' {
+                let pattern = self.pattern[..i].to_string() + "." + &self.pattern[i + 1..];
+                out.push(record(pattern, self.groups.clone()));
+            }
+        }
+
+        return out;
+    }
+}
+
+fn groups_of(springs: &[u8]) -> Vec<usize> {
+    return springs.split(|&b| b == b'.')
+        .filter(|g| !g.is_empty())
+        .map(|g| g.len())
+        .collect();
+}
+
+fn arrangements(pattern: &str, groups: &Vec<usize>) -> u64 {
+    let unknown: Vec<usize> = pattern.bytes().enumerate()
+        .filter(|&(_, b)| b == b'?')
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut count = 0;
+    for fill in 0..1u64 << unknown.len() {
+        let mut springs = pattern.as_bytes().to_vec();
+        for (bit, &i) in unknown.iter().enumerate() {
+            springs[i] = if fill & (1 << bit) != 0 { b'#' } else { b'.' };
+        }
+        if groups_of(&springs) == *groups {
+            count += 1;
+        }
+    }
+
+    return count;
+}
+
+fn gen_record(rng: &mut Rng, max_len: u64) -> Record {
+    let pattern = (0..rng.between(1, max_len)).map(|_| *rng.pick(&['?', '?', '.', '#'])).collect();
+    let groups = (0..rng.between(1, 3)).map(|_| rng.between(1, 3) as usize).collect();
+    return Record{ pattern, groups };
+}
+
+fn record_input(record: &Record) -> String {
+    let groups: Vec<String> = record.groups.iter().map(|g| g.to_string()).collect();
+    return format!("{} {}\n", record.pattern, groups.join(","));
+}
+
+#[test]
+fn day12() {
+    prop::check("day12", 2000, |rng| gen_record(rng, 12), |record| {
+        let input = record_input(record);
+        let expected = arrangements(&record.pattern, &record.groups);
+        return with_input(prop::same(answer::<day12::Day12>(&input, Part::One), Some(expected.into())), &input);
+    });
+}
+
+#[test]
+fn day12_unfolded() {
+    let few_unknowns = |rng: &mut Rng| {
+        loop {
+            let record = gen_record(rng, 4);
+            if record.pattern.matches('?').count() <= 2 {
+                return record;
+            }
+        }
+    };
+
+    prop::check("day12 unfolded", 300, few_unknowns, |record| {
+        if record.pattern.matches('?').count() > 2 {
+            return Ok(());
+        }
+
+        let input = record_input(record);
+        let pattern = [record.pattern.as_str(); 5].join("?");
+        let expected = arrangements(&pattern, &record.groups.repeat(5));
+        return with_input(prop::same(answer::<day12::Day12>(&input, Part::Two), Some(expected.into())), &input);
+    });
+}
+
+// Day 22: working out which bricks hold up which, against letting the whole
+// lot fall again without each one
+
+// One corner, which way the brick goes from it (x, y or z) and how far
+type Brick = ((u32, u32, u32), u32, u32);
+
+fn cubes(brick: &Brick) -> Vec<(u32, u32, u32)> {
+    let ((x, y, z), axis, len) = *brick;
+    return (0..=len).map(|i| match axis {
+        0 => (x + i, y, z),
+        1 => (x, y + i, z),
+        _ => (x, y, z + i),
+    }).collect();
+}
+
+fn bricks_input(bricks: &Vec<Brick>) -> String {
+    let mut input = String::new();
+    for brick in bricks {
+        let cubes = cubes(brick);
+        let (a, b) = (cubes[0], cubes[cubes.len() - 1]);
+        input += &format!("{},{},{}~{},{},{}\n", a.0, a.1, a.2, b.0, b.1, b.2);
+    }
+
+    return input;
+}
+
+// Above the ground, and not in each other
+fn valid_bricks(bricks: &Vec<Brick>) -> bool {
+    let mut seen = BTreeSet::new();
+    for brick in bricks {
+        for cube in cubes(brick) {
+            if cube.2 == 0 || !seen.insert(cube) {
+                return false;
+            }
+        }
+    }
+
+    return true;
+}
+
+// Moves the bricks down a step at a time until none of them can go any
+// further, returning how many moved at all
+fn settle(bricks: &mut Vec<Vec<(u32, u32, u32)>>) -> usize {
+    let mut moved = vec![false; bricks.len()];
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for i in 0..bricks.len() {
+            let lower: Vec<(u32, u32, u32)> = bricks[i].iter().map(|&(x, y, z)| (x, y, z - 1)).collect();
+            let blocked = lower.iter().any(|c| c.2 == 0)
+                || bricks.iter().enumerate().any(|(j, other)| j != i && lower.iter().any(|c| other.contains(c)));
+            if !blocked {
+                bricks[i] = lower;
+                moved[i] = true;
+                changed = true;
+            }
+        }
+    }
+
+    return moved.iter().filter(|&&m| m).count();
+}
+
+fn gen_bricks(rng: &mut Rng) -> Vec<Brick> {
+    let mut bricks = Vec::new();
+    for _ in 0..rng.between(1, 8) {
+        let brick = ((rng.below(4) as u32, rng.below(4) as u32, rng.between(1, 8) as u32), rng.below(3) as u32, rng.below(3) as u32);
+        bricks.push(brick);
+        if !valid_bricks(&bricks) {
+            bricks.pop();
+        }
+    }
+
+    return bricks;
+}
+
+#[test]
+fn day22() {
+    prop::check("day22", 500, gen_bricks, |bricks| {
+        if !valid_bricks(bricks) {
+            return Ok(());
+        }
+        let input = bricks_input(bricks);
+
+        let mut settled: Vec<Vec<(u32, u32, u32)>> = bricks.iter().map(cubes).collect();
+        settle(&mut settled);
+
+        let mut safe = 0;
+        let mut fallen = 0;
+        for i in 0..settled.len() {
+            let mut without = settled.clone();
+            without.remove(i);
+
+            let moved = settle(&mut without);
+            if moved == 0 {
+                safe += 1;
+            }
+            fallen += moved;
+        }
+
+        with_input(prop::same(answer::<day22::Day22>(&input, Part::One), Some(safe.into())), &input)?;
+        with_input(prop::same(answer::<day22::Day22>(&input, Part::Two), Some(fallen.into())), &input)?;
+        return Ok(());
+    });
+}
+
+// Day 02: the most likely bag built up a cube at a time, against trying
+// every bag of that size
+
+type Hands = Vec<(u8, u8, u8)>;
+
+fn hands_input(hands: &Hands) -> String {
+    let hands: Vec<String> = hands.iter()
+        .map(|(r, g, b)| format!("{} red, {} green, {} blue", r, g, b))
+        .collect();
+    return format!("Game 1: {}\n", hands.join("; "));
+}
+
+fn gen_hands(rng: &mut Rng) -> (Hands, u8) {
+    let hands = (0..rng.between(1, 4))
+        .map(|_| (rng.below(4) as u8, rng.below(4) as u8, rng.below(4) as u8))
+        .collect();
+    return (hands, rng.below(13) as u8);
+}
+
+#[test]
+fn day02_mle() {
+    use day02::inference::{self, Model};
+
+    prop::check("day02_mle", 300, gen_hands, |(hands, size)| {
+        let input = hands_input(hands);
+        let games = day02::Day02::parse(&input).unwrap();
+        if hands.iter().all(|&h| h == (0, 0, 0)) {
+            return Ok(());
+        }
+
+        for model in [Model::WithReplacement, Model::WithoutReplacement] {
+            let mut best = f64::NEG_INFINITY;
+            for r in 0..=*size {
+                for g in 0..=*size - r {
+                    let bag = format!("{} red, {} green, {} blue", r, g, size - r - g).parse().unwrap();
+                    let ln_l = inference::ln_likelihood(&games[0], &bag, model);
+                    // Not f64::max, which would skip over a NaN
+                    if ln_l.is_nan() {
+                        return with_input(Err(format!("{:?}: NaN from {}", model, bag)), &input);
+                    }
+                    if ln_l > best {
+                        best = ln_l;
+                    }
+                }
+            }
+
+            let found = inference::mle(&games, *size as u32, model)
+                .map(|bag| inference::ln_likelihood(&games[0], &bag, model));
+            let expected = if best == f64::NEG_INFINITY { None } else { Some(best) };
+            let close = match (found, expected) {
+                (Some(a), Some(b)) => (a - b).abs() < 1e-9,
+                (a, b) => a == b,
+            };
+            if !close {
+                return with_input(Err(format!("{:?}: got {:?}, but every bag gives {:?}", model, found, expected)), &input);
+            }
+        }
+
+        return Ok(());
+    });
+}
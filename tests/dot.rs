@@ -0,0 +1,54 @@
+// The graphs drawn for `--dot`, from the puzzle examples.
+
+use aoc::days;
+
+fn draw(day: u32, name: &str) -> Option<String> {
+    let fname = format!("{}/tests/samples/{}.txt", env!("CARGO_MANIFEST_DIR"), name);
+    let input = std::fs::read_to_string(&fname).unwrap_or_else(|e| panic!("{}: {}", fname, e));
+
+    let runner = days::get(day).unwrap();
+    return runner.dot(&input).unwrap().map(|dot| dot.to_string());
+}
+
+fn edges<'a>(dot: &'a str, arrow: &str) -> Vec<&'a str> {
+    return dot.lines().filter(|l| l.contains(arrow)).collect();
+}
+
+#[test]
+fn day20_modules() {
+    let dot = draw(20, "20b").unwrap();
+    assert!(dot.starts_with("digraph G {\n"));
+    assert!(dot.contains("\"inv\" [label=\"inv\\nConjunction\", shape=diamond];"));
+    assert_eq!(edges(&dot, " -> ").len(), 6);
+}
+
+#[test]
+fn day23_weights() {
+    let dot = draw(23, "23").unwrap();
+    assert!(dot.contains("\"1,0\" [label=\"start\", color=red, penwidth=3];"));
+    assert!(dot.contains("\"1,0\" -> \"3,5\" [label=\"15\"];"));
+    assert!(edges(&dot, " -> ").iter().all(|e| e.contains("label=")));
+}
+
+#[test]
+fn day25_cut() {
+    let dot = draw(25, "25").unwrap();
+    assert!(dot.starts_with("graph G {\n"));
+    assert_eq!(edges(&dot, " -- ").len(), 33);
+
+    // The three wires to disconnect, from the puzzle
+    let mut cut: Vec<&str> = edges(&dot, " -- ").into_iter()
+        .filter(|e| e.contains("color=red"))
+        .collect();
+    cut.sort();
+    assert_eq!(cut, vec![
+        "    \"bvb\" -- \"cmg\" [color=red, penwidth=3];",
+        "    \"hfx\" -- \"pzl\" [color=red, penwidth=3];",
+        "    \"jqt\" -- \"nvd\" [color=red, penwidth=3];",
+    ]);
+}
+
+#[test]
+fn nothing_to_draw() {
+    assert_eq!(draw(5, "05"), None);
+}
@@ -0,0 +1,157 @@
+// Property tests for the interval sets: random sets of small numbers, checked
+// against the same sets held one member at a time.
+
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use aoc::interval::{Hyperrectangle, IntervalSet};
+use aoc::prop::{self, Rng};
+
+const UNIVERSE: u32 = 64;
+const CASES: usize = 2000;
+
+// A range as (start, end), so it can be shrunk. Including empty and
+// backwards ones, which should be ignored.
+type R = (u32, u32);
+
+fn gen_range(rng: &mut Rng) -> R {
+    let start = rng.below(UNIVERSE as u64) as u32;
+    let len = rng.below(16) as u32;
+    if rng.one_in(10) {
+        return (start, start.saturating_sub(len));
+    }
+    return (start, (start + len).min(UNIVERSE));
+}
+
+fn gen_ranges(rng: &mut Rng) -> Vec<R> {
+    let n = rng.below(6);
+    return (0..n).map(|_| gen_range(rng)).collect();
+}
+
+fn range(r: &R) -> Range<u32> {
+    return r.0..r.1;
+}
+
+fn set(ranges: &[R]) -> IntervalSet<u32> {
+    return ranges.iter().map(range).collect();
+}
+
+fn members(ranges: &[Range<u32>]) -> BTreeSet<u32> {
+    return ranges.iter().flat_map(|r| r.clone()).collect();
+}
+
+fn all_members(ranges: &[R]) -> BTreeSet<u32> {
+    return ranges.iter().flat_map(range).collect();
+}
+
+fn check_normalised(s: &IntervalSet<u32>) {
+    let ranges = s.ranges();
+    assert!(ranges.iter().all(|r| r.start < r.end), "empty range in {:?}", ranges);
+    for w in ranges.windows(2) {
+        assert!(w[0].end < w[1].start, "unsorted, overlapping or touching: {:?}", ranges);
+    }
+}
+
+fn check_same(s: &IntervalSet<u32>, expected: &BTreeSet<u32>) {
+    check_normalised(s);
+    assert_eq!(&members(s.ranges()), expected);
+    assert_eq!(s.len(), expected.len() as u128);
+    assert_eq!(s.is_empty(), expected.is_empty());
+    assert_eq!(s.min(), expected.first().copied());
+    for v in 0..UNIVERSE + 2 {
+        assert_eq!(s.contains(&v), expected.contains(&v), "{} in {:?}", v, s.ranges());
+    }
+}
+
+#[test]
+fn construction() {
+    prop::check("construction", CASES, gen_ranges, |ranges| {
+        let s = set(ranges);
+        check_same(&s, &all_members(ranges));
+
+        // The order they're added in makes no difference
+        let mut t = IntervalSet::new();
+        for r in ranges.iter().rev() {
+            t.insert(range(r));
+        }
+        assert_eq!(s, t);
+        return Ok(());
+    });
+}
+
+#[test]
+fn set_operations() {
+    prop::check("set_operations", CASES, |rng| (gen_ranges(rng), gen_ranges(rng)), |(ra, rb)| {
+        let (a, b) = (set(ra), set(rb));
+        let (ma, mb) = (all_members(ra), all_members(rb));
+
+        check_same(&a.union(&b), &ma.union(&mb).copied().collect());
+        check_same(&a.intersection(&b), &ma.intersection(&mb).copied().collect());
+        check_same(&a.difference(&b), &ma.difference(&mb).copied().collect());
+
+        // Some identities, for good measure
+        assert_eq!(a.union(&b), b.union(&a));
+        assert_eq!(a.intersection(&b), b.intersection(&a));
+        assert_eq!(a.difference(&b).union(&a.intersection(&b)), a);
+        assert!(a.difference(&b).intersection(&b).is_empty());
+        assert_eq!(a.union(&b).len() + a.intersection(&b).len(), a.len() + b.len());
+        return Ok(());
+    });
+}
+
+#[test]
+fn splitting() {
+    let gen = |rng: &mut Rng| (gen_ranges(rng), rng.below(UNIVERSE as u64 + 1) as u32);
+    prop::check("splitting", CASES, gen, |(ranges, at)| {
+        let s = set(ranges);
+        let at = *at;
+
+        let (lo, hi) = s.split_at(at);
+        let m = all_members(ranges);
+        check_same(&lo, &m.iter().copied().filter(|&v| v < at).collect());
+        check_same(&hi, &m.iter().copied().filter(|&v| v >= at).collect());
+        assert_eq!(lo.union(&hi), s);
+        return Ok(());
+    });
+}
+
+type Box3 = (R, R, R);
+
+fn gen_box(rng: &mut Rng) -> Box3 {
+    return (gen_range(rng), gen_range(rng), gen_range(rng));
+}
+
+fn hyperrectangle(b: &Box3) -> Hyperrectangle<u32, 3> {
+    return Hyperrectangle::new([range(&b.0), range(&b.1), range(&b.2)]);
+}
+
+#[test]
+fn hyperrectangles() {
+    // Where to cut the first one, as (dimension, at)
+    let gen = |rng: &mut Rng| (gen_box(rng), gen_box(rng), (rng.below(3) as usize, rng.below(UNIVERSE as u64) as u32));
+    prop::check("hyperrectangles", CASES / 40, gen, |(a, b, (dim, at))| {
+        let (a, b) = (hyperrectangle(a), hyperrectangle(b));
+        let both = a.intersection(&b);
+
+        let mut count = 0;
+        for x in 0..UNIVERSE {
+            for y in 0..UNIVERSE {
+                for z in 0..UNIVERSE {
+                    let p = [x, y, z];
+                    assert_eq!(both.contains(&p), a.contains(&p) && b.contains(&p));
+                    if a.contains(&p) {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        assert_eq!(a.volume(), count);
+        assert_eq!(a.is_empty(), count == 0);
+
+        // Cutting it up loses nothing and the halves don't overlap
+        let (lo, hi) = a.split_at(*dim, *at);
+        assert_eq!(lo.volume() + hi.volume(), a.volume());
+        assert!(lo.intersection(&hi).is_empty());
+        return Ok(());
+    });
+}
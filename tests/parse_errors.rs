@@ -0,0 +1,123 @@
+// Malformed inputs should give an error pointing at the problem, rather than
+// a panic.
+
+use aoc::days::*;
+use aoc::error::ParseErr;
+use aoc::solution::{Part, Solution};
+
+// (line, column, token) of the error from parsing `input`
+fn error_at<S: Solution>(input: &str) -> (usize, usize, String) {
+    let e = match S::parse(input) {
+        Ok(_) => panic!("parsing {:?} should fail", input),
+        Err(e) => e,
+    };
+    let e = e.downcast::<ParseErr>().expect("should be a ParseErr");
+
+    return (e.line, e.col, e.token);
+}
+
+#[test]
+fn bad_number() {
+    let input = "Game 1: 3 blue, 4 red\nGame 2: 1 blue, x green\n";
+    assert_eq!(error_at::<day02::Day02>(input), (2, 17, "x".to_string()));
+
+    let input = "0 3 6 9\n1 3 6 1O\n";
+    assert_eq!(error_at::<day09::Day09>(input), (2, 7, "1O".to_string()));
+
+    // Each fits, but not all run together for part 2
+    let input = "Time: 7 15\nDistance: 9 9999999999999999999\n";
+    assert_eq!(error_at::<day06::Day06>(input), (2, 11, "9 9999999999999999999".to_string()));
+}
+
+#[test]
+fn bad_token() {
+    let input = "32T3K 765\nT55X5 684\n";
+    assert_eq!(error_at::<day07::Day07>(input), (2, 1, "T55X5".to_string()));
+
+    let input = "R 6 (#70c710)\nD 5 (#0dc576)\n";
+    assert_eq!(error_at::<day18::Day18>(input), (2, 5, "(#0dc576)".to_string()));
+
+    let input = "in{x<10:A,zz}\n\n{x=1,m=2,a=3,s=4}\n";
+    assert_eq!(error_at::<day19::Day19>(input), (1, 11, "zz".to_string()));
+
+    let input = "in{x<10:A}\n\n{x=1,m=2,a=3,s=4}\n";
+    assert_eq!(error_at::<day19::Day19>(input), (1, 11, "".to_string()));
+
+    let input = "LR\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, CCC)\n";
+    assert_eq!(error_at::<day08::Day08>(input), (4, 13, "CCC".to_string()));
+
+    let input = "LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, CCC)\n";
+    assert_eq!(error_at::<day08::Day08>(input), (3, 8, "BBB".to_string()));
+
+    let input = "\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+    assert_eq!(error_at::<day08::Day08>(input), (1, 1, "".to_string()));
+
+    let input = "32T3K 765\nT55J 684\n";
+    assert_eq!(error_at::<day07::Day07>(input), (2, 1, "T55J".to_string()));
+
+    let input = "1,0,1~1,2,1\n0,0,2~2,0,q\n";
+    assert_eq!(error_at::<day22::Day22>(input), (2, 11, "q".to_string()));
+}
+
+#[test]
+fn bad_grid() {
+    let input = "..#\n.#\n...\n";
+    assert_eq!(error_at::<day14::Day14>(input), (2, 1, ".#".to_string()));
+
+    let input = "2413\n32x5\n";
+    assert_eq!(error_at::<day17::Day17>(input), (2, 3, "x".to_string()));
+}
+
+#[test]
+fn truncated() {
+    let input = "seeds: 79 14 55 13\n";
+    assert_eq!(error_at::<day05::Day05>(input), (2, 1, "".to_string()));
+
+    let input = "Time:      7  15   30\n";
+    assert_eq!(error_at::<day06::Day06>(input), (2, 1, "".to_string()));
+
+    let input = "";
+    assert_eq!(error_at::<day16::Day16>(input), (1, 1, "".to_string()));
+
+    let input = "";
+    assert_eq!(error_at::<day20::Day20>(input), (1, 1, "".to_string()));
+
+    let input = "";
+    assert_eq!(error_at::<day23::Day23>(input), (1, 1, "".to_string()));
+
+    // Winning copies of cards which aren't there
+    let input = "Card 1: 1 2 | 3 4\nCard 2: 1 2 | 1 2\n";
+    assert_eq!(error_at::<day04::Day04>(input), (2, 1, "Card 2".to_string()));
+}
+
+// The message from solving `input`, for inputs which parse but can't be solved
+fn solve_error<S: Solution>(input: &str, part: Part) -> String {
+    return match S::solve(input, part) {
+        Ok(answer) => panic!("solving {:?} should fail, got {}", input, answer),
+        Err(e) => e.to_string(),
+    };
+}
+
+#[test]
+fn unsolvable() {
+    // The loop runs off the bottom
+    let input = "-L|F7\n7S-7|\nL|7||\n";
+    assert_eq!(solve_error::<day10::Day10>(input, Part::One), "pipe leads off the map at (3, 2)");
+
+    // rx is fed by a flip-flop, not a conjunction
+    let input = "broadcaster -> a\n%a -> rx\n";
+    assert_eq!(solve_error::<day20::Day20>(input, Part::Two), "a feeds rx, but it isn't a conjunction");
+
+    // Nothing sends i a pulse ("-i" is some other module), so it never
+    // sends one on to f
+    let input = "broadcaster -> a\n%a -> b, -i\n%b -> c\n%c -> f\n&i -> f\n&f -> rx\n";
+    assert_eq!(solve_error::<day20::Day20>(input, Part::Two), "i never goes high 3 times");
+
+    // Going round and round AAA
+    let input = "LR\n\nAAA = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n";
+    assert_eq!(solve_error::<day08::Day08>(input, Part::One), "ZZZ can't be reached from AAA");
+
+    // Mirrored both ways, between the last two rows and after column 5
+    let input = "#.##..##.\n..#.##.#.\n##......#\n##......#\n";
+    assert_eq!(solve_error::<day13::Day13>(input, Part::One), "pattern 1 has both a vertical and a horizontal mirror");
+}
@@ -0,0 +1,93 @@
+// The pictures drawn for `--draw` and `--frames`, from the puzzle examples.
+
+use aoc::days;
+use aoc::picture::{self, Picture};
+
+fn draw(day: u32, name: &str) -> Vec<Picture> {
+    let fname = format!("{}/tests/samples/{}.txt", env!("CARGO_MANIFEST_DIR"), name);
+    let input = std::fs::read_to_string(&fname).unwrap_or_else(|e| panic!("{}: {}", fname, e));
+
+    let runner = days::get(day).unwrap();
+    return runner.draw(&input).unwrap();
+}
+
+fn title(pic: &Picture) -> String {
+    return pic.ansi().lines().next().unwrap().to_string();
+}
+
+// Where `f` says yes, left to right then top to bottom
+fn cells<F: Fn(&picture::Cell) -> bool>(pic: &Picture, f: F) -> Vec<(usize, usize)> {
+    let mut found = Vec::new();
+    for y in 0..pic.height() {
+        for x in 0..pic.width() {
+            if f(pic.cell((x, y))) {
+                found.push((x, y));
+            }
+        }
+    }
+
+    return found;
+}
+
+#[test]
+fn day10_loop() {
+    let frames = draw(10, "10b");
+    assert_eq!(frames.len(), 1);
+    assert_eq!(title(&frames[0]), "loop of 160, 10 inside");
+    assert_eq!(cells(&frames[0], |c| c.bg == Some(picture::GREEN)).len(), 10);
+    assert_eq!(cells(&frames[0], |c| c.fg == Some(picture::YELLOW)).len(), 159);
+}
+
+#[test]
+fn day14_spin_cycles() {
+    let frames = draw(14, "14");
+    assert_eq!(frames.len(), 10);
+    assert_eq!(title(&frames[0]), "after 0 cycles, load 104");
+    assert_eq!(title(&frames[3]), "after 3 cycles, load 69");
+}
+
+#[test]
+fn day16_energized() {
+    let frames = draw(16, "16");
+    assert_eq!(title(&frames[0]), "from (0, 0) going East, 46 energized");
+    assert_eq!(title(&frames[1]), "from (3, 0) going South, 51 energized");
+    assert_eq!(cells(&frames[1], |c| c.bg == Some(picture::YELLOW)).len(), 51);
+}
+
+#[test]
+fn day17_routes() {
+    let frames = draw(17, "17");
+    assert_eq!(title(&frames[0]), "part 1, heat loss 102");
+    assert_eq!(title(&frames[1]), "part 2, heat loss 94");
+
+    // Every square has its heat loss shaded, and the route starts off along
+    // the top
+    assert!(cells(&frames[0], |c| c.bg.is_none()).is_empty());
+    assert_eq!(frames[1].cell((0, 0)).ch, '>');
+}
+
+#[test]
+fn day21_distances() {
+    let frames = draw(21, "21");
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].width(), 11 * 9);
+    assert_eq!(title(&frames[1]), "2453 reachable in 64 steps");
+    assert_eq!(cells(&frames[1], |c| c.ch == 'O').len(), 2453);
+}
+
+#[test]
+fn day23_longest_hikes() {
+    let frames = draw(23, "23");
+    assert_eq!(title(&frames[0]), "part 1, 94 steps");
+    assert_eq!(title(&frames[1]), "part 2, 154 steps");
+
+    // Every step along the way, and the square at the end
+    for (frame, steps) in frames.iter().zip([94, 154]) {
+        assert_eq!(cells(frame, |c| c.fg.is_some()).len(), steps + 1);
+    }
+}
+
+#[test]
+fn nothing_to_draw() {
+    assert!(draw(5, "05").is_empty());
+}
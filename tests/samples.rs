@@ -0,0 +1,163 @@
+// Runs the worked examples from each day's puzzle text, which are checked in
+// under tests/samples/, against the answers given there.
+
+use aoc::days::*;
+use aoc::solution::{Answer, Part, Solution};
+
+fn sample(name: &str) -> String {
+    let fname = format!("{}/tests/samples/{}.txt", env!("CARGO_MANIFEST_DIR"), name);
+    return std::fs::read_to_string(&fname).unwrap_or_else(|e| panic!("{}: {}", fname, e));
+}
+
+fn check<S: Solution>(name: &str, part: Part, expected: i128) {
+    let answer = S::solve(&sample(name), part).unwrap();
+    assert_eq!(answer, Answer::Int(expected), "sample {} part {}", name, part);
+}
+
+fn check_both<S: Solution>(name: &str, part1: i128, part2: i128) {
+    check::<S>(name, Part::One, part1);
+    check::<S>(name, Part::Two, part2);
+}
+
+#[test]
+fn day01() {
+    check::<day01::Day01>("01", Part::One, 142);
+    check::<day01::Day01>("01b", Part::Two, 281);
+}
+
+#[test]
+fn day02() {
+    check_both::<day02::Day02>("02", 8, 2286);
+}
+
+#[test]
+fn day03() {
+    check_both::<day03::Day03>("03", 4361, 467835);
+}
+
+#[test]
+fn day04() {
+    check_both::<day04::Day04>("04", 13, 30);
+}
+
+#[test]
+fn day05() {
+    check_both::<day05::Day05>("05", 35, 46);
+}
+
+#[test]
+fn day06() {
+    check_both::<day06::Day06>("06", 288, 71503);
+}
+
+#[test]
+fn day07() {
+    check_both::<day07::Day07>("07", 6440, 5905);
+}
+
+#[test]
+fn day08() {
+    check_both::<day08::Day08>("08", 2, 2);
+    check_both::<day08::Day08>("08b", 6, 6);
+    check::<day08::Day08>("08c", Part::Two, 6);
+    // Ghosts which take a while to get into their loops
+    check::<day08::Day08>("08d", Part::Two, 7);
+}
+
+#[test]
+fn day09() {
+    check_both::<day09::Day09>("09", 114, 2);
+}
+
+#[test]
+fn day10() {
+    check::<day10::Day10>("10", Part::One, 8);
+    check::<day10::Day10>("10b", Part::Two, 10);
+}
+
+#[test]
+fn day11() {
+    check::<day11::Day11>("11", Part::One, 374);
+
+    // The example uses smaller expansions than the real thing
+    let universe = day11::Day11::parse(&sample("11")).unwrap();
+    assert_eq!(universe.sum_distances(10), 1030);
+    assert_eq!(universe.sum_distances(100), 8410);
+}
+
+#[test]
+fn day12() {
+    check_both::<day12::Day12>("12", 21, 525152);
+}
+
+#[test]
+fn day13() {
+    check_both::<day13::Day13>("13", 405, 400);
+}
+
+#[test]
+fn day14() {
+    check_both::<day14::Day14>("14", 136, 64);
+}
+
+#[test]
+fn day15() {
+    check_both::<day15::Day15>("15", 1320, 145);
+}
+
+#[test]
+fn day16() {
+    check_both::<day16::Day16>("16", 46, 51);
+}
+
+#[test]
+fn day17() {
+    check_both::<day17::Day17>("17", 102, 94);
+}
+
+#[test]
+fn day18() {
+    check_both::<day18::Day18>("18", 62, 952408144115);
+}
+
+#[test]
+fn day19() {
+    check_both::<day19::Day19>("19", 19114, 167409079868000);
+}
+
+#[test]
+fn day20() {
+    check::<day20::Day20>("20", Part::One, 32000000);
+    check::<day20::Day20>("20b", Part::One, 11687500);
+    // A small counter in place of the real input's, as neither example has rx
+    check::<day20::Day20>("20c", Part::Two, 4);
+}
+
+#[test]
+fn day21() {
+    // The example only takes 6 steps rather than 64
+    let garden = day21::Day21::parse(&sample("21")).unwrap();
+    assert_eq!(day21::reachable(&garden, 6), 16);
+}
+
+#[test]
+fn day22() {
+    check_both::<day22::Day22>("22", 5, 7);
+}
+
+#[test]
+fn day23() {
+    check_both::<day23::Day23>("23", 94, 154);
+}
+
+#[test]
+fn day24() {
+    // The example's test area is much smaller than the real one
+    let hailstones = day24::Day24::parse(&sample("24")).unwrap();
+    assert_eq!(day24::count_intersections(&hailstones, 7, 27), 2);
+}
+
+#[test]
+fn day25() {
+    check::<day25::Day25>("25", Part::One, 54);
+}